@@ -45,6 +45,33 @@ impl Graph {
     pub fn num_commits(&self) -> u32 {
         self.files.iter().map(File::num_commits).sum()
     }
+
+    /// Return the smallest amount of hex characters needed to unambiguously identify `id` among all commits in this graph,
+    /// or `None` if `id` isn't stored in it.
+    ///
+    /// Note that the abbreviation is only unique among commits in this graph, other objects may still share it.
+    pub fn unique_hex_len(&self, id: impl AsRef<gix_hash::oid>) -> Option<usize> {
+        let id = id.as_ref();
+        let mut is_contained = false;
+        let mut longest_common_hex_len = 0;
+        for file in &self.files {
+            let (before, after) = match file.lookup_inner(id) {
+                Ok(pos) => {
+                    is_contained = true;
+                    (pos.0.checked_sub(1), pos.0 + 1)
+                }
+                Err(pos) => (pos.0.checked_sub(1), pos.0),
+            };
+            for neighbor in before
+                .into_iter()
+                .chain(Some(after).filter(|pos| *pos < file.num_commits()))
+            {
+                longest_common_hex_len =
+                    longest_common_hex_len.max(common_hex_len(id, file.id_at(file::Position(neighbor))));
+            }
+        }
+        is_contained.then(|| (longest_common_hex_len + 1).min(id.kind().len_in_hex()))
+    }
 }
 
 /// Access fundamentals
@@ -95,3 +122,18 @@ struct LookupByPositionResult<'a> {
     pub _file_index: usize,
     pub pos: file::Position,
 }
+
+fn common_hex_len(a: &gix_hash::oid, b: &gix_hash::oid) -> usize {
+    let mut len = 0;
+    for (a, b) in a.as_bytes().iter().zip(b.as_bytes()) {
+        if a == b {
+            len += 2;
+        } else {
+            if a >> 4 == b >> 4 {
+                len += 1;
+            }
+            break;
+        }
+    }
+    len
+}
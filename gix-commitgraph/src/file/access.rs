@@ -73,10 +73,12 @@ impl File {
     /// Translate the given object hash to its position within this file, if present.
     // copied from gix-odb/src/pack/index/ext
     pub fn lookup(&self, id: impl AsRef<gix_hash::oid>) -> Option<file::Position> {
-        self.lookup_inner(id.as_ref())
+        self.lookup_inner(id.as_ref()).ok()
     }
 
-    fn lookup_inner(&self, id: &gix_hash::oid) -> Option<file::Position> {
+    /// Return `Ok(position)` of `id` if it's contained in this file, or `Err(position)` at which it would have to be inserted
+    /// to keep all ids sorted.
+    pub(crate) fn lookup_inner(&self, id: &gix_hash::oid) -> Result<file::Position, file::Position> {
         let first_byte = usize::from(id.first_byte());
         let mut upper_bound = self.fan[first_byte];
         let mut lower_bound = if first_byte != 0 { self.fan[first_byte - 1] } else { 0 };
@@ -88,11 +90,11 @@ impl File {
             use std::cmp::Ordering::*;
             match id.cmp(mid_sha) {
                 Less => upper_bound = mid,
-                Equal => return Some(file::Position(mid)),
+                Equal => return Ok(file::Position(mid)),
                 Greater => lower_bound = mid + 1,
             }
         }
-        None
+        Err(file::Position(lower_bound))
     }
 
     /// Returns the number of commits in this graph file.
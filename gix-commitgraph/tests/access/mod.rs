@@ -111,3 +111,34 @@ fn two_parents() {
     assert_eq!(cg.commit_at(refs["parent2"].pos()).generation(), 1);
     assert_eq!(cg.commit_at(refs["child"].pos()).generation(), 2);
 }
+
+#[test]
+fn unique_hex_len() {
+    for (script, refs) in [
+        (
+            "octopus_merges.sh",
+            &["root", "parent1", "parent2", "four_parents"] as &[_],
+        ),
+        ("split_chain.sh", &["commit1", "commit2", "commit3"]),
+    ] {
+        let (cg, _refs) = graph_and_expected(script, refs);
+        let ids: Vec<_> = cg.iter_ids().map(ToOwned::to_owned).collect();
+        for id in &ids {
+            let hex = id.to_hex().to_string();
+            let shares_prefix_with_other = |len: usize| {
+                ids.iter()
+                    .filter(|other| *other != id)
+                    .any(|other| other.to_hex().to_string().starts_with(&hex[..len]))
+            };
+            let expected = (1..=hex.len())
+                .find(|len| !shares_prefix_with_other(*len))
+                .expect("ids are unique");
+            assert_eq!(cg.unique_hex_len(id), Some(expected), "{script}: {id}");
+        }
+        assert_eq!(
+            cg.unique_hex_len(gix_hash::Kind::Sha1.null()),
+            None,
+            "ids that aren't in the graph have no length"
+        );
+    }
+}
@@ -93,7 +93,13 @@ mod relative {
     use crate::parse::Error;
 
     fn parse_inner(input: &str) -> Option<Duration> {
-        let mut split = input.split_whitespace();
+        if input == "yesterday" {
+            return Some(Duration::days(1));
+        }
+        // Like `git`, accept dots as separators, as in `2.weeks.ago`, which avoids quoting on the command-line.
+        let mut split = input
+            .split(|c: char| c.is_whitespace() || c == '.')
+            .filter(|token| !token.is_empty());
         let multiplier = i64::from_str(split.next()?).ok()?;
         let period = split.next()?;
         if split.next()? != "ago" {
@@ -138,6 +144,12 @@ mod relative {
         #[test]
         fn two_weeks_ago() {
             assert_eq!(parse_inner("2 weeks ago"), Some(Duration::weeks(2)));
+            assert_eq!(parse_inner("2.weeks.ago"), Some(Duration::weeks(2)));
+        }
+
+        #[test]
+        fn yesterday() {
+            assert_eq!(parse_inner("yesterday"), Some(Duration::days(1)));
         }
     }
 }
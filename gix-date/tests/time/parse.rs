@@ -182,6 +182,23 @@ mod relative {
             "relative times differ"
         );
     }
+
+    #[test]
+    fn dots_as_separators_and_yesterday() {
+        let now = SystemTime::now();
+        for (input, expected) in [("2.days.ago", Duration::days(2)), ("yesterday", Duration::days(1))] {
+            let time = gix_date::parse(input, Some(now)).unwrap();
+            let expected = OffsetDateTime::from(now)
+                .saturating_sub(expected)
+                .replace_nanosecond(0)
+                .unwrap();
+            assert_eq!(
+                OffsetDateTime::from_unix_timestamp(time.seconds).unwrap(),
+                expected,
+                "{input}"
+            );
+        }
+    }
 }
 
 /// Various cases the fuzzer found
@@ -25,6 +25,8 @@ pub enum Error {
         action: &'static str,
         path: PathBuf,
     },
+    #[error("Object {id} is not stored as loose object, but is available in a pack")]
+    NotLoose { id: gix_hash::ObjectId },
 }
 
 /// Object lookup
@@ -111,7 +113,37 @@ impl Store {
         out: &'a mut Vec<u8>,
     ) -> Result<Option<gix_object::Data<'a>>, Error> {
         debug_assert_eq!(self.object_hash, id.kind());
-        match self.find_inner(id, out) {
+        Ok(Self::none_if_missing(self.find_inner(id, out, false))?
+            .map(|(kind, _header_size)| gix_object::Data { kind, data: out }))
+    }
+
+    /// Like [`try_find()`](Self::try_find()), but write the decompressed object including its `<kind> <size>\0` header
+    /// into `out` and return it along with the object itself, which borrows its data from it.
+    ///
+    /// The raw bytes are exactly what's hashed to produce `id`, which is useful to verify or re-store objects without
+    /// decompressing them twice.
+    pub fn try_find_with_raw<'a>(
+        &self,
+        id: &gix_hash::oid,
+        out: &'a mut Vec<u8>,
+    ) -> Result<Option<(gix_object::Data<'a>, &'a [u8])>, Error> {
+        debug_assert_eq!(self.object_hash, id.kind());
+        Ok(
+            Self::none_if_missing(self.find_inner(id, out, true))?.map(|(kind, header_size)| {
+                let raw: &'a [u8] = out;
+                (
+                    gix_object::Data {
+                        kind,
+                        data: &raw[header_size..],
+                    },
+                    raw,
+                )
+            }),
+        )
+    }
+
+    fn none_if_missing<T>(res: Result<T, Error>) -> Result<Option<T>, Error> {
+        match res {
             Ok(obj) => Ok(Some(obj)),
             Err(err) => match err {
                 Error::Io {
@@ -134,9 +166,38 @@ impl Store {
         }
     }
 
+    /// Like [`try_find()`](Self::try_find()), but if `id` doesn't exist as loose object, call `is_packed(id)` to learn
+    /// whether it's stored in a pack instead and return [`Error::NotLoose`] if so.
+    ///
+    /// This is useful in setups that read loose objects and packs separately, to let callers know they have to consult
+    /// a pack database instead of assuming the object doesn't exist at all.
+    pub fn try_find_loose<'a>(
+        &self,
+        id: &gix_hash::oid,
+        out: &'a mut Vec<u8>,
+        is_packed: &mut dyn FnMut(&gix_hash::oid) -> bool,
+    ) -> Result<Option<gix_object::Data<'a>>, Error> {
+        match self.try_find(id, out)? {
+            Some(data) => Ok(Some(data)),
+            None if is_packed(id) => Err(Error::NotLoose { id: id.to_owned() }),
+            None => Ok(None),
+        }
+    }
+
     /// Return only the decompressed size of the object and its kind without fully reading it into memory as tuple of `(size, kind)`.
     /// Returns `None` if `id` does not exist in the database.
     pub fn try_header(&self, id: &gix_hash::oid) -> Result<Option<(u64, gix_object::Kind)>, Error> {
+        Ok(self.header_inner(id)?.map(|(kind, size, _header_size)| (size, kind)))
+    }
+
+    /// Return the amount of bytes that the `<kind> <size>\0` header of the decompressed object occupies,
+    /// without reading the object's content.
+    /// Returns `None` if `id` does not exist in the database.
+    pub fn try_header_size(&self, id: &gix_hash::oid) -> Result<Option<usize>, Error> {
+        Ok(self.header_inner(id)?.map(|(_kind, _size, header_size)| header_size))
+    }
+
+    fn header_inner(&self, id: &gix_hash::oid) -> Result<Option<(gix_object::Kind, u64, usize)>, Error> {
         const BUF_SIZE: usize = 256;
         let mut buf = [0_u8; BUF_SIZE];
         let path = hash_path(id, self.path.clone());
@@ -175,11 +236,16 @@ impl Store {
                 path,
             });
         }
-        let (kind, size, _header_size) = gix_object::decode::loose_header(&header_buf[..consumed_out])?;
-        Ok(Some((size, kind)))
+        Ok(Some(gix_object::decode::loose_header(&header_buf[..consumed_out])?))
     }
 
-    fn find_inner<'a>(&self, id: &gix_hash::oid, buf: &'a mut Vec<u8>) -> Result<gix_object::Data<'a>, Error> {
+    /// Decompress the object `id` into `buf`, with its header if `keep_header` is true, and return its kind and the size of its header.
+    fn find_inner(
+        &self,
+        id: &gix_hash::oid,
+        buf: &mut Vec<u8>,
+        keep_header: bool,
+    ) -> Result<(gix_object::Kind, usize), Error> {
         let path = hash_path(id, self.path.clone());
 
         let mut inflate = zlib::Inflate::default();
@@ -218,10 +284,11 @@ impl Store {
         let decompressed_start = bytes_read;
         let (kind, size, header_size) =
             gix_object::decode::loose_header(&buf[decompressed_start..decompressed_start + consumed_out])?;
+        let kept_header_size = if keep_header { header_size } else { 0 };
 
         if status == zlib::Status::StreamEnd {
-            let decompressed_body_bytes_sans_header =
-                decompressed_start + header_size..decompressed_start + consumed_out;
+            let decompressed_body_bytes =
+                decompressed_start + header_size - kept_header_size..decompressed_start + consumed_out;
 
             if consumed_out as u64 != size + header_size as u64 {
                 return Err(Error::SizeMismatch {
@@ -230,7 +297,7 @@ impl Store {
                     path,
                 });
             }
-            buf.copy_within(decompressed_body_bytes_sans_header, 0);
+            buf.copy_within(decompressed_body_bytes, 0);
         } else {
             let new_len = bytes_read as u64 + size + header_size as u64;
             buf.resize(new_len.try_into().map_err(|_| Error::OutOfMemory { size: new_len })?, 0);
@@ -254,13 +321,13 @@ impl Store {
                     });
                 }
             };
-            buf.copy_within(decompressed_start + header_size.., 0);
+            buf.copy_within(decompressed_start + header_size - kept_header_size.., 0);
         }
         buf.resize(
-            size.try_into()
-                .expect("BUG: here the size is already confirmed to fit into memory"),
+            usize::try_from(size).expect("BUG: here the size is already confirmed to fit into memory")
+                + kept_header_size,
             0,
         );
-        Ok(gix_object::Data { kind, data: buf })
+        Ok((kind, header_size))
     }
 }
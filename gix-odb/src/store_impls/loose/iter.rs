@@ -5,6 +5,36 @@ use crate::store_impls::loose;
 /// Returned by [`loose::Store::iter()`]
 pub type Error = gix_features::fs::walkdir::Error;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod disk_usage {
+    use std::path::PathBuf;
+
+    /// The error returned by [`loose::Store::disk_usage()`][crate::loose::Store::disk_usage()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Iter(#[from] super::Error),
+        #[error("Could not obtain the size of loose object at '{}'", path.display())]
+        Metadata { source: std::io::Error, path: PathBuf },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod iter_with_kind {
+    /// The error returned by [`loose::Store::iter_with_kind()`][crate::loose::Store::iter_with_kind()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Iter(#[from] super::Error),
+        #[error(transparent)]
+        Header(#[from] crate::loose::find::Error),
+    }
+}
+
 impl loose::Iter {
     fn path_to_id(
         &self,
@@ -79,4 +109,41 @@ impl loose::Store {
             hash_hex_len: self.object_hash.len_in_hex(),
         }
     }
+
+    /// Return an iterator over all objects contained in the database along with their kind.
+    ///
+    /// Only the header of each object is decompressed to learn its kind, which is much cheaper than reading its content.
+    /// Objects that are deleted while iterating, for instance by a concurrent `git gc`, are skipped.
+    pub fn iter_with_kind(
+        &self,
+    ) -> impl Iterator<Item = Result<(gix_hash::ObjectId, gix_object::Kind), iter_with_kind::Error>> + '_ {
+        self.iter().filter_map(move |id| {
+            let id = match id {
+                Ok(id) => id,
+                Err(err) => return Some(Err(err.into())),
+            };
+            self.try_header(&id)
+                .map_err(Into::into)
+                .transpose()
+                .map(|res| res.map(|(_size, kind)| (id, kind)))
+        })
+    }
+
+    /// Return the total amount of bytes that all loose objects take on disk, not accounting for the file-system's block size.
+    ///
+    /// Only files that [`iter()`][loose::Store::iter()] would return as object are counted, so packs and other files
+    /// in the object directory are ignored.
+    /// Objects that are deleted while the computation runs, for instance by a concurrent `git gc`, are skipped.
+    pub fn disk_usage(&self) -> Result<u64, disk_usage::Error> {
+        let mut bytes = 0;
+        for id in self.iter() {
+            let path = loose::hash_path(&id?, self.path.clone());
+            match std::fs::metadata(&path) {
+                Ok(meta) => bytes += meta.len(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(source) => return Err(disk_usage::Error::Metadata { source, path }),
+            }
+        }
+        Ok(bytes)
+    }
 }
@@ -12,6 +12,8 @@ pub struct Store {
     pub(crate) path: PathBuf,
     /// The kind of hash we should assume during iteration and when writing new objects.
     pub(crate) object_hash: gix_hash::Kind,
+    /// How much effort to make to assure written objects end up on disk.
+    pub(crate) durability: write::Durability,
 }
 
 /// Initialization
@@ -26,9 +28,16 @@ impl Store {
         Store {
             path: objects_directory.into(),
             object_hash,
+            durability: Default::default(),
         }
     }
 
+    /// Use `durability` to control whether written objects are synced to disk before they become visible.
+    pub fn with_durability(mut self, durability: write::Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+
     /// Return the path to our `objects` directory.
     pub fn path(&self) -> &Path {
         &self.path
@@ -38,6 +47,11 @@ impl Store {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+
+    /// Return how much effort is made to assure written objects end up on disk.
+    pub fn durability(&self) -> write::Durability {
+        self.durability
+    }
 }
 
 fn hash_path(id: &gix_hash::oid, mut root: PathBuf) -> PathBuf {
@@ -57,6 +71,12 @@ pub mod find;
 pub mod iter;
 ///
 #[allow(clippy::empty_docs)]
+pub mod remove;
+///
+#[allow(clippy::empty_docs)]
+pub mod stream;
+///
+#[allow(clippy::empty_docs)]
 pub mod verify;
 
 /// The type for an iterator over `Result<gix_hash::ObjectId, Error>)`
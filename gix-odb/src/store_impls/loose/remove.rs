@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use crate::store_impls::loose;
+
+/// The error returned by [`loose::Store::remove()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Refusing to remove '{}' as it isn't a valid loose object", path.display())]
+    InvalidObject { source: loose::find::Error, path: PathBuf },
+    #[error("Could not remove loose object at '{}'", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+}
+
+/// Removal
+impl loose::Store {
+    /// Delete the file of the loose object with `id` and return `true`, or return `false` if there was no such file.
+    ///
+    /// Unless `force` is `true`, the file is only removed if its header can be decoded, to avoid removing stray files
+    /// that merely look like objects by their path. Note that it's up to the caller to assure that the object
+    /// isn't needed anymore, for instance because it's unreachable or also available in a pack.
+    pub fn remove(&self, id: &gix_hash::oid, force: bool) -> Result<bool, Error> {
+        let path = loose::hash_path(id, self.path.clone());
+        if !force {
+            match self.try_header(id) {
+                Ok(Some(_)) => {}
+                Ok(None) => return Ok(false),
+                Err(source) => return Err(Error::InvalidObject { source, path }),
+            }
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(source) => Err(Error::Io { source, path }),
+        }
+    }
+}
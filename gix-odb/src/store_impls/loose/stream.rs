@@ -0,0 +1,122 @@
+use std::{
+    fs,
+    io::{self, BufReader},
+    ops::Range,
+};
+
+use gix_features::zlib;
+
+use crate::store_impls::loose::{find::Error, hash_path, Store, HEADER_MAX_SIZE};
+
+/// A reader over the decompressed content of a loose object, without its header, as returned by [`Store::try_stream()`].
+///
+/// The object is decompressed incrementally while reading, so it never has to be held in memory as a whole.
+pub struct Reader {
+    kind: gix_object::Kind,
+    size: u64,
+    /// The amount of content bytes that are yet to be returned.
+    remaining: u64,
+    /// The decompressed header, followed by content bytes that were decompressed along with it.
+    header_buf: [u8; HEADER_MAX_SIZE],
+    /// The range of content bytes in `header_buf` that weren't returned yet.
+    leftover: Range<usize>,
+    inner: BufReader<fs::File>,
+    inflate: zlib::Decompress,
+}
+
+/// Access
+impl Reader {
+    /// Return the kind of the object.
+    pub fn kind(&self) -> gix_object::Kind {
+        self.kind
+    }
+
+    /// Return the size of the object's content in bytes, which is the amount of bytes this reader will produce.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl io::Read for Reader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max = buf.len().min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        if max == 0 {
+            return Ok(0);
+        }
+        let num_read = if !self.leftover.is_empty() {
+            let num_read = self.leftover.len().min(max);
+            let start = self.leftover.start;
+            buf[..num_read].copy_from_slice(&self.header_buf[start..start + num_read]);
+            self.leftover.start += num_read;
+            num_read
+        } else {
+            let num_read = zlib::stream::inflate::read(&mut self.inner, &mut self.inflate, &mut buf[..max])?;
+            if num_read == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "loose object ended after {} of {} bytes",
+                        self.size - self.remaining,
+                        self.size
+                    ),
+                ));
+            }
+            num_read
+        };
+        self.remaining -= num_read as u64;
+        Ok(num_read)
+    }
+}
+
+/// Object lookup
+impl Store {
+    /// Return a reader over the decompressed content of the object identified by `id`, or `None` if it doesn't exist.
+    ///
+    /// As opposed to [`try_find()`](Self::try_find()), only the object's header is decompressed here, which provides
+    /// its [kind](Reader::kind()) and [size](Reader::size()), while its content is decompressed incrementally as it is read.
+    /// This allows to process large objects, like big blobs, without holding them in memory.
+    ///
+    /// Note that the reader fails if the object ends before its size was reached.
+    pub fn try_stream(&self, id: &gix_hash::oid) -> Result<Option<Reader>, Error> {
+        debug_assert_eq!(self.object_hash, id.kind());
+        let path = hash_path(id, self.path.clone());
+        let mut inner = match fs::File::open(&path) {
+            Ok(file) => BufReader::new(file),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::Io {
+                    source: err,
+                    action: "open",
+                    path,
+                })
+            }
+        };
+
+        let mut inflate = zlib::Decompress::new(true);
+        let mut header_buf = [0_u8; HEADER_MAX_SIZE];
+        let num_decompressed =
+            zlib::stream::inflate::read(&mut inner, &mut inflate, &mut header_buf).map_err(|err| Error::Io {
+                source: err,
+                action: "deflate",
+                path: path.clone(),
+            })?;
+        let (kind, size, header_size) = gix_object::decode::loose_header(&header_buf[..num_decompressed])?;
+        let leftover_len = (num_decompressed - header_size) as u64;
+        if leftover_len > size {
+            return Err(Error::SizeMismatch {
+                expected: size + header_size as u64,
+                actual: num_decompressed as u64,
+                path,
+            });
+        }
+        Ok(Some(Reader {
+            kind,
+            size,
+            remaining: size,
+            header_buf,
+            leftover: header_size..num_decompressed,
+            inner,
+            inflate,
+        }))
+    }
+}
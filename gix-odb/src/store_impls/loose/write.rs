@@ -24,6 +24,23 @@ pub enum Error {
         source: tempfile::PersistError,
         target: PathBuf,
     },
+    #[error("Refusing to overwrite object {expected} with data that hashes to {actual}")]
+    HashMismatch {
+        expected: gix_hash::ObjectId,
+        actual: gix_hash::ObjectId,
+    },
+}
+
+/// Determines how much effort is made to assure that written objects survive a system crash or power loss,
+/// as set with [`Store::with_durability()`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Durability {
+    /// Leave it to the operating system to write objects to disk eventually, which is fastest.
+    #[default]
+    Fast,
+    /// Sync each object file to disk before moving it into place, and sync its directory afterwards to persist
+    /// the move as well, similar to `core.fsyncObjectFiles` in `git`.
+    Fsync,
 }
 
 impl crate::traits::Write for Store {
@@ -40,28 +57,14 @@ impl crate::traits::Write for Store {
             path: self.path.to_owned(),
         })?;
         to.flush().map_err(Box::new)?;
-        Ok(self.finalize_object(to).map_err(Box::new)?)
+        Ok(self.finalize_object(to, false).map_err(Box::new)?)
     }
 
     /// Write the given buffer in `from` to disk in one syscall at best.
     ///
     /// This will cost at least 4 IO operations.
     fn write_buf(&self, kind: gix_object::Kind, from: &[u8]) -> Result<gix_hash::ObjectId, crate::write::Error> {
-        let mut to = self.dest().map_err(Box::new)?;
-        to.write_all(&gix_object::encode::loose_header(kind, from.len() as u64))
-            .map_err(|err| Error::Io {
-                source: err,
-                message: "write header to tempfile in",
-                path: self.path.to_owned(),
-            })?;
-
-        to.write_all(from).map_err(|err| Error::Io {
-            source: err,
-            message: "stream all data into tempfile in",
-            path: self.path.to_owned(),
-        })?;
-        to.flush()?;
-        Ok(self.finalize_object(to)?)
+        Ok(self.write_buf_inner(kind, from, false)?)
     }
 
     /// Write the given stream in `from` to disk with at least one syscall.
@@ -89,12 +92,36 @@ impl crate::traits::Write for Store {
             })
             .map_err(Box::new)?;
         to.flush().map_err(Box::new)?;
-        Ok(self.finalize_object(to)?)
+        Ok(self.finalize_object(to, false)?)
     }
 }
 
 type CompressedTempfile = deflate::Write<NamedTempFile>;
 
+/// Repair
+impl Store {
+    /// Write the object of `kind` with `data` to the path of `id`, replacing the loose object stored there,
+    /// which is useful to repair a corrupt object with a correct copy of it.
+    ///
+    /// The new object is written to a temporary file first which is then renamed over the existing one, so readers never
+    /// observe a partially written object. If `data` doesn't hash to `id`, nothing is written and [`Error::HashMismatch`]
+    /// is returned.
+    ///
+    /// On Windows, the read-only attribute of the existing object is removed so it can be replaced, and an error is returned
+    /// if it can't be replaced, for instance because another process has it open.
+    pub fn overwrite(&self, id: &gix_hash::oid, kind: gix_object::Kind, data: &[u8]) -> Result<(), Error> {
+        let actual = gix_object::compute_hash(self.object_hash, kind, data);
+        if actual != id {
+            return Err(Error::HashMismatch {
+                expected: id.to_owned(),
+                actual,
+            });
+        }
+        self.write_buf_inner(kind, data, true)?;
+        Ok(())
+    }
+}
+
 /// Access
 impl Store {
     /// Return the path to the object with `id`.
@@ -106,6 +133,29 @@ impl Store {
 }
 
 impl Store {
+    fn write_buf_inner(
+        &self,
+        kind: gix_object::Kind,
+        from: &[u8],
+        replace_existing: bool,
+    ) -> Result<gix_hash::ObjectId, Error> {
+        let mut to = self.dest()?;
+        to.write_all(&gix_object::encode::loose_header(kind, from.len() as u64))
+            .map_err(|err| Error::Io {
+                source: err,
+                message: "write header to tempfile in",
+                path: self.path.to_owned(),
+            })?;
+
+        to.write_all(from).map_err(|err| Error::Io {
+            source: err,
+            message: "stream all data into tempfile in",
+            path: self.path.to_owned(),
+        })?;
+        to.flush()?;
+        self.finalize_object(to, replace_existing)
+    }
+
     fn dest(&self) -> Result<hash::Write<CompressedTempfile>, Error> {
         #[cfg_attr(not(unix), allow(unused_mut))]
         let mut builder = tempfile::Builder::new();
@@ -125,9 +175,13 @@ impl Store {
         ))
     }
 
+    /// Move the object in `file` into place. If `replace_existing` is `true`, an existing object must be replaced,
+    /// otherwise it's assumed to be the same object and may be kept on Windows.
+    #[cfg_attr(not(windows), allow(unused_variables))]
     fn finalize_object(
         &self,
         hash::Write { hash, inner: file }: hash::Write<CompressedTempfile>,
+        replace_existing: bool,
     ) -> Result<gix_hash::ObjectId, Error> {
         let id = gix_hash::ObjectId::from(hash.digest());
         let object_path = loose::hash_path(&id, self.path.clone());
@@ -141,21 +195,55 @@ impl Store {
             }
         }
         let file = file.into_inner();
+        if self.durability == Durability::Fsync {
+            file.as_file().sync_all().map_err(|err| Error::Io {
+                source: err,
+                message: "sync temporary object file in",
+                path: self.path.to_owned(),
+            })?;
+        }
+        // Read-only files can't be replaced on windows.
+        #[cfg(windows)]
+        if replace_existing {
+            match fs::metadata(&object_path) {
+                Ok(meta) if meta.permissions().readonly() => {
+                    let mut perms = meta.permissions();
+                    #[allow(clippy::permissions_set_readonly_false)]
+                    perms.set_readonly(false);
+                    fs::set_permissions(&object_path, perms)?;
+                }
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
         let res = file.persist(&object_path);
         // On windows, we assume that such errors are due to its special filesystem semantics,
         // on any other platform that would be a legitimate error though.
         #[cfg(windows)]
         if let Err(err) = &res {
-            if err.error.kind() == std::io::ErrorKind::PermissionDenied
-                || err.error.kind() == std::io::ErrorKind::AlreadyExists
+            if !replace_existing
+                && (err.error.kind() == std::io::ErrorKind::PermissionDenied
+                    || err.error.kind() == std::io::ErrorKind::AlreadyExists)
             {
                 return Ok(id);
             }
         }
         res.map_err(|err| Error::Persist {
             source: err,
-            target: object_path,
+            target: object_path.clone(),
         })?;
+        // Directories can't be opened for syncing on windows.
+        #[cfg(unix)]
+        if self.durability == Durability::Fsync {
+            fs::File::open(object_dir)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|err| Error::Io {
+                    source: err,
+                    message: "sync object directory",
+                    path: object_dir.to_owned(),
+                })?;
+        }
         Ok(id)
     }
 }
@@ -31,6 +31,36 @@ fn iter() {
     oids.sort();
     assert_eq!(oids, object_ids());
 }
+
+#[test]
+fn iter_with_kind() -> crate::Result {
+    use gix_object::Kind::*;
+    let mut objects = ldb().iter_with_kind().collect::<Result<Vec<_>, _>>()?;
+    objects.sort();
+    assert_eq!(
+        objects,
+        object_ids()
+            .into_iter()
+            .zip([Blob, Blob, Tree, Tag, Tree, Blob, Commit])
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn disk_usage() -> crate::Result {
+    let db = ldb();
+    let expected: u64 = object_ids()
+        .iter()
+        .map(|id| {
+            let hex = id.to_hex().to_string();
+            std::fs::metadata(db.path().join(&hex[..2]).join(&hex[2..])).map(|m| m.len())
+        })
+        .sum::<std::io::Result<_>>()?;
+    assert_eq!(db.disk_usage()?, expected, "only loose object files are counted");
+    Ok(())
+}
+
 pub fn locate_oid(id: gix_hash::ObjectId, buf: &mut Vec<u8>) -> gix_object::Data<'_> {
     ldb().try_find(&id, buf).expect("read success").expect("id present")
 }
@@ -101,6 +131,81 @@ mod write {
         Ok(())
     }
 
+    #[test]
+    fn overwrite_replaces_corrupt_objects() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let id = db.write_buf(gix_object::Kind::Blob, b"content")?;
+        let path = db.object_path(&id);
+        std::fs::remove_file(&path)?;
+        std::fs::write(&path, b"corrupt")?;
+        let mut buf = Vec::new();
+        assert!(db.try_find(&id, &mut buf).is_err(), "the object can't be read anymore");
+
+        assert!(matches!(
+            db.overwrite(&id, gix_object::Kind::Blob, b"other content"),
+            Err(loose::write::Error::HashMismatch { expected, .. }) if expected == id
+        ));
+        assert_eq!(
+            std::fs::read(&path)?,
+            b"corrupt",
+            "nothing is written if the data doesn't match the id"
+        );
+
+        db.overwrite(&id, gix_object::Kind::Blob, b"content")?;
+        let obj = db.try_find(&id, &mut buf)?.expect("present");
+        assert_eq!(obj.kind, gix_object::Kind::Blob);
+        assert_eq!(obj.data, b"content");
+        Ok(())
+    }
+
+    #[test]
+    fn durable_writes_are_readable() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        assert_eq!(db.durability(), loose::write::Durability::Fast, "this is the default");
+        let db = db.with_durability(loose::write::Durability::Fsync);
+
+        let mut buf = Vec::new();
+        for oid in object_ids() {
+            let expected = locate_oid(oid, &mut buf);
+            let actual = db.write_buf(expected.kind, expected.data)?;
+            assert_eq!(actual, oid);
+        }
+        let mut buf2 = Vec::new();
+        for oid in object_ids() {
+            let expected = locate_oid(oid, &mut buf);
+            let actual = db.try_find(&oid, &mut buf2)?.expect("present");
+            assert_eq!(actual.kind, expected.kind);
+            assert_eq!(actual.data, expected.data);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn blobs_round_trip_byte_by_byte() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let big: Vec<u8> = (0..1024 * 1024).map(|n| (n % 251) as u8).collect();
+        let mut buf = Vec::new();
+        for data in [&b""[..], b"\0binary\xff\r\n", &big] {
+            let id = db.write_buf(gix_object::Kind::Blob, data)?;
+            assert_eq!(id, gix_object::compute_hash(id.kind(), gix_object::Kind::Blob, data));
+
+            let blob = db.try_find(&id, &mut buf)?.expect("just written");
+            assert_eq!(blob.kind, gix_object::Kind::Blob);
+            assert_eq!(blob.data, data, "blobs are decompressed entirely, past their header");
+        }
+
+        let big_id = gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, &big);
+        let hex = big_id.to_hex().to_string();
+        assert!(
+            std::fs::metadata(dir.path().join(&hex[..2]).join(&hex[2..]))?.len() < big.len() as u64,
+            "objects are stored compressed"
+        );
+        Ok(())
+    }
+
     #[test]
     fn collisions_do_not_cause_failure() -> crate::Result {
         let dir = gix_testtools::tempfile::tempdir()?;
@@ -131,6 +236,100 @@ mod write {
     }
 }
 
+mod stream {
+    use std::io::Read;
+
+    use gix_odb::{loose, Write};
+
+    use crate::{hex_to_id, store::loose::ldb};
+
+    #[test]
+    fn all_objects_can_be_streamed() -> crate::Result {
+        let db = ldb();
+        let mut buf = Vec::new();
+        let mut actual = Vec::new();
+        for id in db.iter() {
+            let id = id?;
+            let expected = db.try_find(&id, &mut buf)?.expect("exists");
+            let mut stream = db.try_stream(&id)?.expect("exists");
+            assert_eq!(stream.kind(), expected.kind);
+            assert_eq!(stream.size(), expected.data.len() as u64);
+
+            actual.clear();
+            stream.read_to_end(&mut actual)?;
+            assert_eq!(actual, expected.data, "{id}: the content is streamed without its header");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn big_objects_are_streamed_in_small_chunks() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let big: Vec<u8> = (0..1024 * 1024).map(|n| (n % 251) as u8).collect();
+        let id = db.write_buf(gix_object::Kind::Blob, &big)?;
+
+        let mut stream = db.try_stream(&id)?.expect("just written");
+        assert_eq!(stream.size(), big.len() as u64);
+        let mut chunk = [0u8; 1000];
+        let mut actual = Vec::new();
+        loop {
+            let num_read = stream.read(&mut chunk)?;
+            if num_read == 0 {
+                break;
+            }
+            actual.extend_from_slice(&chunk[..num_read]);
+        }
+        assert!(actual == big, "all data is returned, in order");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_objects_yield_none() -> crate::Result {
+        assert!(ldb()
+            .try_stream(&hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab989"))?
+            .is_none());
+        Ok(())
+    }
+}
+
+mod remove {
+    use gix_odb::{loose, Write};
+
+    #[test]
+    fn written_objects_can_be_removed() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let id = db.write_buf(gix_object::Kind::Blob, b"content")?;
+        assert!(db.contains(&id));
+
+        assert!(db.remove(&id, false)?, "the object existed");
+        assert!(!db.contains(&id));
+        assert!(!db.remove(&id, false)?, "it's gone");
+        assert!(!db.remove(&id, true)?, "it's gone even if forced");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_objects_are_only_removed_if_forced() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let id = db.write_buf(gix_object::Kind::Blob, b"content")?;
+        let path = db.object_path(&id);
+        std::fs::remove_file(&path)?;
+        std::fs::write(&path, b"not an object")?;
+
+        assert!(matches!(
+            db.remove(&id, false),
+            Err(loose::remove::Error::InvalidObject { .. })
+        ));
+        assert!(path.is_file(), "stray files are kept");
+        assert!(db.remove(&id, true)?);
+        assert!(!path.exists());
+        Ok(())
+    }
+}
+
 mod contains {
     use crate::store::loose::ldb;
 
@@ -338,6 +537,45 @@ cjHJZXWmV4CcRfmLsXzU8s2cR9A0DBvOxhPD1TlKC2JhBFXigjuL9U4Rbq9tdegB
         assert_eq!(try_locate("37d4e6c5c48ba0d245164c4e10d5f41140cab989", &mut buf), None);
     }
 
+    #[test]
+    fn packed_objects_are_reported_if_a_pack_hook_is_provided() -> crate::Result {
+        let index = gix_pack::index::File::at(
+            gix_testtools::fixture_path_standalone("objects/pack/pack-11fdfa9e156ab73caae3b6da867192221f2089c2.idx"),
+            gix_hash::Kind::Sha1,
+        )?;
+        let db = ldb();
+        let packed_id = index
+            .iter()
+            .map(|entry| entry.oid)
+            .find(|id| !db.contains(id))
+            .expect("at least one object is only in the pack");
+        let mut buf = Vec::new();
+        let err = db
+            .try_find_loose(&packed_id, &mut buf, &mut |id| index.lookup(id).is_some())
+            .unwrap_err();
+        assert!(
+            matches!(err, loose::find::Error::NotLoose { id } if id == packed_id),
+            "the hook makes the lookup fail with a distinct error"
+        );
+        assert!(
+            db.try_find(&packed_id, &mut buf)?.is_none(),
+            "without the hook, the object just doesn't exist"
+        );
+
+        let missing = hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab989");
+        assert!(db
+            .try_find_loose(&missing, &mut buf, &mut |id| index.lookup(id).is_some())?
+            .is_none());
+
+        let loose_id = hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980");
+        assert!(db
+            .try_find_loose(&loose_id, &mut buf, &mut |_| unreachable!(
+                "not called for loose objects"
+            ))?
+            .is_some());
+        Ok(())
+    }
+
     #[test]
     fn blob_big() -> Result<(), Box<dyn std::error::Error>> {
         let mut buf = Vec::new();
@@ -387,6 +625,32 @@ cjHJZXWmV4CcRfmLsXzU8s2cR9A0DBvOxhPD1TlKC2JhBFXigjuL9U4Rbq9tdegB
         Ok(())
     }
 
+    #[test]
+    fn with_raw() -> crate::Result {
+        let db = ldb();
+        let mut buf = Vec::new();
+        let mut raw_buf = Vec::new();
+        for id in db.iter() {
+            let id = id?;
+            let expected = db.try_find(&id, &mut buf)?.expect("exists");
+            let (obj, raw) = db.try_find_with_raw(&id, &mut raw_buf)?.expect("exists");
+            assert_eq!(obj.kind, expected.kind);
+            assert_eq!(obj.data, expected.data);
+
+            let mut hasher = gix_features::hash::hasher(id.kind());
+            hasher.update(raw);
+            assert_eq!(
+                gix_hash::ObjectId::from(hasher.digest()),
+                id,
+                "the raw bytes include the header and hash to the id"
+            );
+        }
+        assert!(db
+            .try_find_with_raw(&hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"), &mut raw_buf)?
+            .is_none());
+        Ok(())
+    }
+
     mod header {
         use crate::odb::{hex_to_id, store::loose::ldb};
 
@@ -425,6 +689,31 @@ cjHJZXWmV4CcRfmLsXzU8s2cR9A0DBvOxhPD1TlKC2JhBFXigjuL9U4Rbq9tdegB
             }
             Ok(())
         }
+
+        #[test]
+        fn size() -> crate::Result {
+            let db = ldb();
+            assert_eq!(
+                db.try_header_size(&hex_to_id("a706d7cd20fc8ce71489f34b50cf01011c104193"))?,
+                Some("blob 56915".len() + 1),
+                "the header is `<kind> <size>` followed by a null byte"
+            );
+            assert_eq!(
+                db.try_header_size(&hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"))?,
+                None,
+                "it does not exist"
+            );
+
+            for id in db.iter() {
+                let id = id?;
+                let (size, kind) = db.try_header(&id)?.expect("header exists");
+                assert_eq!(
+                    db.try_header_size(&id)?.expect("header exists"),
+                    format!("{kind} {size}").len() + 1
+                );
+            }
+            Ok(())
+        }
     }
 }
 
@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn pattern_matching_relative_path(c: &mut Criterion) {
+    let specs: Vec<_> = (0..20)
+        .map(|idx| format!("dir-{idx}/sub/*.rs"))
+        .chain(Some("deep/d1/d2/d3/d4/d5/*.txt".into()))
+        .map(|spec| gix_pathspec::parse(spec.as_bytes(), Default::default()).expect("valid"))
+        .collect();
+    let mut search = gix_pathspec::Search::from_specs(specs, None, Path::new("")).expect("valid");
+    let paths: Vec<String> = (0..500)
+        .map(|idx| format!("deep/d1/d2/d3/d4/d5/file-{idx}.txt"))
+        .collect();
+
+    c.bench_function("Search::pattern_matching_relative_path() in deep tree", |b| {
+        b.iter(|| {
+            let mut count = 0;
+            for path in &paths {
+                count += usize::from(
+                    search
+                        .pattern_matching_relative_path(path.as_str().into(), Some(false), &mut |_, _, _, _| false)
+                        .is_some(),
+                );
+            }
+            black_box(count)
+        })
+    });
+}
+
+criterion_group!(benches, pattern_matching_relative_path);
+criterion_main!(benches);
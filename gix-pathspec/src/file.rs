@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use bstr::{BStr, ByteSlice};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod split {
+    /// The error returned by [`split()`](super::split()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line {line_number} is badly quoted")]
+        Unquote {
+            line_number: usize,
+            source: gix_quote::ansi_c::undo::Error,
+        },
+        #[error("Line {line_number} starts with a quote, but misses the closing quote")]
+        MissingClosingQuote { line_number: usize },
+    }
+}
+
+/// Split `input`, the contents of a file with one pathspec per line as passed to `git <cmd> --pathspec-from-file=<file>`,
+/// into the pathspecs it contains, ready to be [parsed](crate::parse()).
+///
+/// If `nul_separated` is `true`, pathspecs are separated by NUL bytes and taken verbatim, just like `--pathspec-file-nul` would.
+/// Otherwise, they are separated by LF or CRLF, and lines that start with `"` are unquoted like C strings, so pathspecs that
+/// contain newlines can be expressed as well.
+///
+/// A separator after the last pathspec is optional, and empty input yields no pathspec.
+/// Note that `#` doesn't start a comment, and that empty lines yield empty pathspecs, which are invalid, just like in `git`.
+pub fn split(input: &[u8], nul_separated: bool) -> impl Iterator<Item = Result<Cow<'_, BStr>, split::Error>> + '_ {
+    let separator = if nul_separated { b'\0' } else { b'\n' };
+    let lines = (!input.is_empty()).then(|| {
+        input
+            .strip_suffix(&[separator])
+            .unwrap_or(input)
+            .split(move |b| *b == separator)
+    });
+    lines.into_iter().flatten().enumerate().map(move |(line_idx, line)| {
+        if nul_separated {
+            return Ok(line.as_bstr().into());
+        }
+        let line_number = line_idx + 1;
+        let line = line.strip_suffix(b"\r").unwrap_or(line).as_bstr();
+        if line.starts_with(b"\"") && !has_closing_quote(line) {
+            return Err(split::Error::MissingClosingQuote { line_number });
+        }
+        gix_quote::ansi_c::undo(line)
+            .map(|(unquoted, _consumed)| unquoted)
+            .map_err(|source| split::Error::Unquote { line_number, source })
+    })
+}
+
+/// Return `true` if the quoted `line` contains a closing quote that isn't escaped.
+fn has_closing_quote(line: &[u8]) -> bool {
+    let mut bytes = line.iter().skip(1);
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'\\' => {
+                bytes.next();
+            }
+            b'"' => return true,
+            _ => {}
+        }
+    }
+    false
+}
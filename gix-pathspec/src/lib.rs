@@ -36,6 +36,10 @@ pub mod search;
 #[allow(clippy::empty_docs)]
 pub mod parse;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod file;
+
 /// Default settings for some fields of a [`Pattern`].
 ///
 /// These can be used to represent `GIT_*_PATHSPECS` environment variables, for example.
@@ -77,6 +81,9 @@ pub struct Search {
     all_patterns_are_excluded: bool,
     /// The amount of bytes that are in common among all `patterns` and that aren't matched case-insensitively
     common_prefix_len: usize,
+    /// Remember which `patterns` can possibly match within the directory of the last matched path, to skip all others
+    /// for subsequent paths in the same directory.
+    dir_cache: search::DirectoryCache,
 }
 
 /// The output of a pathspec [parsing][parse()] operation. It can be used to match against a one or more paths.
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::{search::Spec, MagicSignature, Pattern, Search};
+use crate::{search::Spec, MagicSignature, Pattern, Search, SearchMode};
 
 /// Create a new specification to support matches from `pathspec`, [normalizing](Pattern::normalize()) it with `prefix` and `root`.
 fn mapping_from_pattern(
@@ -25,6 +25,10 @@ fn mapping_from_pattern(
         if pathspec.signature.contains(MagicSignature::MUST_BE_DIR) {
             g.mode |= gix_glob::pattern::Mode::MUST_BE_DIR;
         }
+        if pathspec.search_mode == SearchMode::Literal {
+            // Wildcards have no special meaning, so the whole path can be used for prefix-matching and pruning.
+            g.first_wildcard_pos = None;
+        }
         g
     };
 
@@ -47,6 +51,53 @@ fn mapping_from_pattern(
     })
 }
 
+/// Return `narrow` if it is known to only match paths that `wide` matches as well, adjusted to also match only
+/// directories if `wide` requires it. If both have the same path, the one without wildcards is returned instead.
+fn narrower_subset(
+    wide: &gix_glob::search::pattern::Mapping<Spec>,
+    narrow: &gix_glob::search::pattern::Mapping<Spec>,
+) -> Option<gix_glob::search::pattern::Mapping<Spec>> {
+    let (wide_spec, narrow_spec) = (&wide.value.pattern, &narrow.value.pattern);
+    if wide_spec.always_matches() {
+        return Some(narrow.clone());
+    }
+    if wide.value.attrs_match.is_some()
+        || (narrow_spec.signature.contains(MagicSignature::ICASE)
+            && !wide_spec.signature.contains(MagicSignature::ICASE))
+    {
+        return None;
+    }
+    let literal_len = |m: &gix_glob::search::pattern::Mapping<Spec>| {
+        m.pattern.first_wildcard_pos.unwrap_or(m.value.pattern.path.len())
+    };
+    if literal_len(wide) != wide_spec.path.len() {
+        return None;
+    }
+
+    let narrow_literal = &narrow_spec.path[..literal_len(narrow)];
+    if narrow_spec.path == wide_spec.path {
+        // With the same path, a glob like `src*` matches more than `:(literal)src*`, so the pattern without wildcards is kept.
+        let (mut out, other_spec) = if literal_len(narrow) == narrow_spec.path.len() {
+            (narrow.clone(), wide_spec)
+        } else if narrow.value.attrs_match.is_none()
+            && narrow_spec.signature.contains(MagicSignature::ICASE)
+                == wide_spec.signature.contains(MagicSignature::ICASE)
+        {
+            (wide.clone(), narrow_spec)
+        } else {
+            return None;
+        };
+        if other_spec.signature.contains(MagicSignature::MUST_BE_DIR) {
+            out.value.pattern.signature |= MagicSignature::MUST_BE_DIR;
+            out.pattern.mode |= gix_glob::pattern::Mode::MUST_BE_DIR;
+        }
+        Some(out)
+    } else {
+        (narrow_literal.starts_with(&wide_spec.path) && narrow_literal.get(wide_spec.path.len()) == Some(&b'/'))
+            .then(|| narrow.clone())
+    }
+}
+
 fn common_prefix_len(patterns: &[gix_glob::search::pattern::Mapping<Spec>]) -> usize {
     let mut count = 0;
     let len = patterns
@@ -119,26 +170,75 @@ impl Search {
                 )?);
             }
 
-            // Excludes should always happen first so we know a match is authoritative (otherwise we could find a non-excluding match first).
-            patterns.sort_by(|a, b| {
-                a.value
-                    .pattern
-                    .is_excluded()
-                    .cmp(&b.value.pattern.is_excluded())
-                    .reverse()
-            });
-
-            let common_prefix_len = common_prefix_len(&patterns);
-            Ok(Search {
-                all_patterns_are_excluded: patterns.iter().all(|s| s.value.pattern.is_excluded()),
-                patterns,
-                source: None,
-                common_prefix_len,
-            })
+            Ok(Search::from_mappings(patterns))
         }
         inner(&mut pathspecs.into_iter(), prefix, root)
     }
 
+    /// Return a search that matches only paths that are matched by both `self` and `other`, or `None` if no path
+    /// can be matched by both.
+    ///
+    /// As intersecting patterns with wildcards isn't generally possible, this is done conservatively: of each pair
+    /// of included patterns, the more specific one is kept if it's known to only match paths that the other one
+    /// matches as well, while pairs that can't be proven to overlap are dropped. Excluded patterns of both are kept.
+    /// Thus, the result may match fewer paths than a precise intersection would, but never more.
+    pub fn intersect(&self, other: &Search) -> Option<Search> {
+        fn includes(search: &Search) -> Vec<&gix_glob::search::pattern::Mapping<Spec>> {
+            search
+                .patterns
+                .iter()
+                .filter(|m| !m.value.pattern.is_excluded())
+                .collect()
+        }
+        let (lhs, rhs) = (includes(self), includes(other));
+        let mut patterns: Vec<_> = if lhs.is_empty() {
+            rhs.into_iter().cloned().collect()
+        } else if rhs.is_empty() {
+            lhs.into_iter().cloned().collect()
+        } else {
+            let patterns: Vec<_> = lhs
+                .iter()
+                .flat_map(|a| rhs.iter().map(move |b| (*a, *b)))
+                .filter_map(|(a, b)| narrower_subset(a, b).or_else(|| narrower_subset(b, a)))
+                .collect();
+            if patterns.is_empty() {
+                return None;
+            }
+            patterns
+        };
+        patterns.extend(
+            self.patterns
+                .iter()
+                .chain(other.patterns.iter())
+                .filter(|m| m.value.pattern.is_excluded())
+                .cloned(),
+        );
+        for (idx, mapping) in patterns.iter_mut().enumerate() {
+            mapping.sequence_number = idx;
+        }
+        Some(Search::from_mappings(patterns))
+    }
+
+    fn from_mappings(mut patterns: Vec<gix_glob::search::pattern::Mapping<Spec>>) -> Search {
+        // Excludes should always happen first so we know a match is authoritative (otherwise we could find a non-excluding match first).
+        patterns.sort_by(|a, b| {
+            a.value
+                .pattern
+                .is_excluded()
+                .cmp(&b.value.pattern.is_excluded())
+                .reverse()
+        });
+
+        let common_prefix_len = common_prefix_len(&patterns);
+        Search {
+            all_patterns_are_excluded: patterns.iter().all(|s| s.value.pattern.is_excluded()),
+            patterns,
+            source: None,
+            common_prefix_len,
+            dir_cache: Default::default(),
+        }
+    }
+
     /// Obtain ownership of the normalized pathspec patterns that were used for the search.
     pub fn into_patterns(self) -> impl Iterator<Item = Pattern> {
         self.patterns.into_iter().map(|p| p.value.pattern)
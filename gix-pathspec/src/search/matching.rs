@@ -8,6 +8,16 @@ use crate::{
     MagicSignature, Pattern, Search, SearchMode,
 };
 
+/// Matches everything, as if `:` was provided, and is used if there is no pattern to match with.
+static MATCH_ALL_STAND_IN: Pattern = Pattern {
+    path: BString::new(Vec::new()),
+    signature: MagicSignature::empty(),
+    search_mode: SearchMode::ShellGlob,
+    attributes: Vec::new(),
+    prefix_len: 0,
+    nil: true,
+};
+
 impl Search {
     /// Return the first [`Match`] of `relative_path`, or `None`.
     /// `is_dir` is `true` if `relative_path` is a directory, or assumed `false` if `None`.
@@ -31,103 +41,81 @@ impl Search {
         is_dir: Option<bool>,
         attributes: &mut dyn FnMut(&BStr, Case, bool, &mut gix_attributes::search::Outcome) -> bool,
     ) -> Option<Match<'_>> {
-        static MATCH_ALL_STAND_IN: Pattern = Pattern {
-            path: BString::new(Vec::new()),
-            signature: MagicSignature::empty(),
-            search_mode: SearchMode::ShellGlob,
-            attributes: Vec::new(),
-            prefix_len: 0,
-            nil: true,
-        };
         if relative_path.is_empty() {
-            return Some(Match {
-                pattern: &MATCH_ALL_STAND_IN,
-                sequence_number: 0,
-                kind: Always,
-            });
+            return Some(match_all(0));
         }
-        let basename_not_important = None;
-        if relative_path
-            .get(..self.common_prefix_len)
-            .map_or(true, |rela_path_prefix| rela_path_prefix != self.common_prefix())
-        {
+        if !self.has_common_prefix(relative_path) {
             return None;
         }
+        let first = self
+            .matching_pattern_indices(relative_path, is_dir.unwrap_or(false), attributes)
+            .next();
+        match first {
+            Some((idx, kind)) => Some(self.match_at(idx, kind)),
+            None => self.all_patterns_are_excluded.then(|| match_all(self.patterns.len())),
+        }
+    }
 
-        let is_dir = is_dir.unwrap_or(false);
-        let patterns_len = self.patterns.len();
-        let res = self.patterns.iter_mut().find_map(|mapping| {
-            let ignore_case = mapping.value.pattern.signature.contains(MagicSignature::ICASE);
-            let prefix = mapping.value.pattern.prefix_directory();
-            if ignore_case && !prefix.is_empty() {
-                let pattern_requirement_is_met = relative_path.get(prefix.len()).map_or_else(|| is_dir, |b| *b == b'/');
-                if !pattern_requirement_is_met
-                    || relative_path.get(..prefix.len()).map(ByteSlice::as_bstr) != Some(prefix)
-                {
-                    return None;
-                }
-            }
-
-            let case = if ignore_case { Case::Fold } else { Case::Sensitive };
-            let mut is_match = mapping.value.pattern.always_matches();
-            let mut how = Always;
-            if !is_match {
-                is_match = if mapping.pattern.first_wildcard_pos.is_none() {
-                    match_verbatim(mapping, relative_path, is_dir, case, &mut how)
-                } else {
-                    let wildmatch_mode = match mapping.value.pattern.search_mode {
-                        SearchMode::ShellGlob => Some(gix_glob::wildmatch::Mode::empty()),
-                        SearchMode::Literal => None,
-                        SearchMode::PathAwareGlob => Some(gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL),
-                    };
-                    match wildmatch_mode {
-                        Some(wildmatch_mode) => {
-                            let is_match = mapping.pattern.matches_repo_relative_path(
-                                relative_path,
-                                basename_not_important,
-                                Some(is_dir),
-                                case,
-                                wildmatch_mode,
-                            );
-                            if !is_match {
-                                match_verbatim(mapping, relative_path, is_dir, case, &mut how)
-                            } else {
-                                how = mapping.pattern.first_wildcard_pos.map_or(Verbatim, |_| WildcardMatch);
-                                true
-                            }
-                        }
-                        None => match_verbatim(mapping, relative_path, is_dir, case, &mut how),
-                    }
-                }
-            }
+    /// Return all [matches](Match) of `relative_path` in order of precedence, which is empty if there is none.
+    /// The first match, if present, is the one that [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path())
+    /// would return, and thus decides if `relative_path` is [excluded](Match::is_excluded()).
+    /// For all other parameters, see [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path()).
+    ///
+    /// Use this to learn about all patterns that apply to `relative_path`, for instance to explain why it was included or excluded.
+    /// Note that if all patterns are excluded, the implied pattern that matches everything is returned last.
+    pub fn all_pattern_matches(
+        &mut self,
+        relative_path: &BStr,
+        is_dir: Option<bool>,
+        attributes: &mut dyn FnMut(&BStr, Case, bool, &mut gix_attributes::search::Outcome) -> bool,
+    ) -> Vec<Match<'_>> {
+        if relative_path.is_empty() {
+            return vec![match_all(0)];
+        }
+        if !self.has_common_prefix(relative_path) {
+            return Vec::new();
+        }
+        let matches: Vec<_> = self
+            .matching_pattern_indices(relative_path, is_dir.unwrap_or(false), attributes)
+            .collect();
+        let mut out: Vec<_> = matches
+            .into_iter()
+            .map(|(idx, kind)| self.match_at(idx, kind))
+            .collect();
+        if self.all_patterns_are_excluded {
+            out.push(match_all(self.patterns.len()));
+        }
+        out
+    }
 
-            if let Some(attrs) = mapping.value.attrs_match.as_mut() {
-                if !attributes(relative_path, Case::Sensitive, is_dir, attrs) {
-                    // we have attrs, but it didn't match any
-                    return None;
-                }
-                for (actual, expected) in attrs.iter_selected().zip(mapping.value.pattern.attributes.iter()) {
-                    if actual.assignment != expected.as_ref() {
-                        return None;
-                    }
-                }
-            }
+    fn has_common_prefix(&self, relative_path: &BStr) -> bool {
+        relative_path.get(..self.common_prefix_len) == Some(self.common_prefix().as_bytes())
+    }
 
-            is_match.then_some(Match {
-                pattern: &mapping.value.pattern,
-                sequence_number: mapping.sequence_number,
-                kind: how,
+    /// Return the index of each pattern that matches `relative_path` along with how it matched, in order of precedence.
+    fn matching_pattern_indices<'a>(
+        &'a mut self,
+        relative_path: &'a BStr,
+        is_dir: bool,
+        attributes: &'a mut dyn FnMut(&BStr, Case, bool, &mut gix_attributes::search::Outcome) -> bool,
+    ) -> impl Iterator<Item = (usize, MatchKind)> + 'a {
+        let candidates = self.dir_cache.candidates(&self.patterns, relative_path);
+        self.patterns
+            .iter_mut()
+            .zip(candidates)
+            .enumerate()
+            .filter_map(|(idx, (mapping, is_candidate))| is_candidate.then_some((idx, mapping)))
+            .filter_map(move |(idx, mapping)| {
+                match_mapping(mapping, relative_path, is_dir, attributes).map(|kind| (idx, kind))
             })
-        });
+    }
 
-        if res.is_none() && self.all_patterns_are_excluded {
-            Some(Match {
-                pattern: &MATCH_ALL_STAND_IN,
-                sequence_number: patterns_len,
-                kind: Always,
-            })
-        } else {
-            res
+    fn match_at(&self, idx: usize, kind: MatchKind) -> Match<'_> {
+        let mapping = &self.patterns[idx];
+        Match {
+            pattern: &mapping.value.pattern,
+            sequence_number: mapping.sequence_number,
+            kind,
         }
     }
 
@@ -249,6 +237,79 @@ impl Search {
     }
 }
 
+fn match_all(sequence_number: usize) -> Match<'static> {
+    Match {
+        pattern: &MATCH_ALL_STAND_IN,
+        sequence_number,
+        kind: Always,
+    }
+}
+
+/// Return how the pattern in `mapping` matches `relative_path`, or `None` if it doesn't match.
+fn match_mapping(
+    mapping: &mut gix_glob::search::pattern::Mapping<Spec>,
+    relative_path: &BStr,
+    is_dir: bool,
+    attributes: &mut dyn FnMut(&BStr, Case, bool, &mut gix_attributes::search::Outcome) -> bool,
+) -> Option<MatchKind> {
+    let basename_not_important = None;
+    let ignore_case = mapping.value.pattern.signature.contains(MagicSignature::ICASE);
+    let prefix = mapping.value.pattern.prefix_directory();
+    if ignore_case && !prefix.is_empty() {
+        let pattern_requirement_is_met = relative_path.get(prefix.len()).map_or_else(|| is_dir, |b| *b == b'/');
+        if !pattern_requirement_is_met || relative_path.get(..prefix.len()).map(ByteSlice::as_bstr) != Some(prefix) {
+            return None;
+        }
+    }
+
+    let case = if ignore_case { Case::Fold } else { Case::Sensitive };
+    let mut is_match = mapping.value.pattern.always_matches();
+    let mut how = Always;
+    if !is_match {
+        is_match = if mapping.pattern.first_wildcard_pos.is_none() {
+            match_verbatim(mapping, relative_path, is_dir, case, &mut how)
+        } else {
+            let wildmatch_mode = match mapping.value.pattern.search_mode {
+                SearchMode::ShellGlob => Some(gix_glob::wildmatch::Mode::empty()),
+                SearchMode::Literal => None,
+                SearchMode::PathAwareGlob => Some(gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL),
+            };
+            match wildmatch_mode {
+                Some(wildmatch_mode) => {
+                    let is_match = mapping.pattern.matches_repo_relative_path(
+                        relative_path,
+                        basename_not_important,
+                        Some(is_dir),
+                        case,
+                        wildmatch_mode,
+                    );
+                    if !is_match {
+                        match_verbatim(mapping, relative_path, is_dir, case, &mut how)
+                    } else {
+                        how = mapping.pattern.first_wildcard_pos.map_or(Verbatim, |_| WildcardMatch);
+                        true
+                    }
+                }
+                None => match_verbatim(mapping, relative_path, is_dir, case, &mut how),
+            }
+        }
+    }
+
+    if let Some(attrs) = mapping.value.attrs_match.as_mut() {
+        if !attributes(relative_path, Case::Sensitive, is_dir, attrs) {
+            // we have attrs, but it didn't match any
+            return None;
+        }
+        for (actual, expected) in attrs.iter_selected().zip(mapping.value.pattern.attributes.iter()) {
+            if actual.assignment != expected.as_ref() {
+                return None;
+            }
+        }
+    }
+
+    is_match.then_some(how)
+}
+
 fn match_verbatim(
     mapping: &gix_glob::search::pattern::Mapping<Spec>,
     relative_path: &BStr,
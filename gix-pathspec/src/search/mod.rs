@@ -1,4 +1,4 @@
-use bstr::{BStr, ByteSlice};
+use bstr::{BStr, BString, ByteSlice};
 use std::borrow::Cow;
 use std::path::Path;
 
@@ -31,6 +31,57 @@ pub enum MatchKind {
 
 mod init;
 
+/// The patterns that can possibly match paths within `dir`, one entry per pattern of a [`Search`].
+///
+/// As patterns and their case-sensitivity can't change after the search was created, the cache stays valid
+/// until a path in another directory is matched.
+#[derive(Default, Debug, Clone)]
+pub(crate) struct DirectoryCache {
+    /// The leading directory of the last matched path, including the trailing `/`, or empty for top-level paths.
+    dir: BString,
+    /// `true` at the index of each pattern that might match paths in `dir`.
+    candidates: Vec<bool>,
+}
+
+impl DirectoryCache {
+    /// Return the candidate flags for all `patterns` in the directory of `relative_path`,
+    /// recomputing them only if the directory changed.
+    pub(crate) fn candidates(
+        &mut self,
+        patterns: &[gix_glob::search::pattern::Mapping<Spec>],
+        relative_path: &BStr,
+    ) -> &[bool] {
+        let dir = &relative_path[..relative_path.rfind_byte(b'/').map_or(0, |pos| pos + 1)];
+        if self.candidates.len() != patterns.len() || self.dir != dir {
+            self.dir.clear();
+            self.dir.extend_from_slice(dir);
+            self.candidates.clear();
+            self.candidates.extend(
+                patterns
+                    .iter()
+                    .map(|mapping| can_match_in_directory(mapping, dir.as_bstr())),
+            );
+        }
+        &self.candidates
+    }
+}
+
+/// Return `false` if the literal portion of the pattern in `mapping` conflicts with `dir`, so it can't match any path within it.
+fn can_match_in_directory(mapping: &gix_glob::search::pattern::Mapping<Spec>, dir: &BStr) -> bool {
+    let pattern = &mapping.value.pattern;
+    if pattern.always_matches() {
+        return true;
+    }
+    let max_usable_pattern_len = mapping.pattern.first_wildcard_pos.unwrap_or(pattern.path.len());
+    let common_len = max_usable_pattern_len.min(dir.len());
+    let (pattern_path, dir) = (pattern.path[..common_len].as_bstr(), &dir[..common_len]);
+    if pattern.signature.contains(MagicSignature::ICASE) {
+        pattern_path.eq_ignore_ascii_case(dir)
+    } else {
+        pattern_path == dir
+    }
+}
+
 impl Match<'_> {
     /// Return `true` if the pathspec that matched was negative, which excludes this item from the set.
     pub fn is_excluded(&self) -> bool {
@@ -0,0 +1,68 @@
+use bstr::BString;
+use gix_pathspec::file::split;
+
+fn lines(input: &str, nul_separated: bool) -> crate::Result<Vec<BString>> {
+    Ok(split(input.as_bytes(), nul_separated)
+        .map(|line| line.map(std::borrow::Cow::into_owned))
+        .collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn newline_separated() -> crate::Result {
+    assert!(lines("", false)?.is_empty(), "empty files contain no pathspec");
+    assert_eq!(lines("a\nb/*", false)?, ["a", "b/*"]);
+    assert_eq!(
+        lines("a\nb/*\n", false)?,
+        ["a", "b/*"],
+        "a trailing newline is optional"
+    );
+    assert_eq!(lines("a\r\n:!b\r\n", false)?, ["a", ":!b"], "CRLF is supported");
+    assert_eq!(
+        lines("# not a comment\n\n", false)?,
+        ["# not a comment", ""],
+        "comments don't exist, and empty lines are kept to fail when parsed"
+    );
+    assert_eq!(
+        lines("\"with\\nnewline\"\n\"\\303\\244\"\nno\"unquoting\"", false)?,
+        ["with\nnewline", "ä", "no\"unquoting\""],
+        "lines starting with a quote are unquoted"
+    );
+    assert_eq!(
+        lines("\"\"\n\"a\"ignored", false)?,
+        ["", "a"],
+        "content after the closing quote is ignored"
+    );
+    assert_eq!(lines("a\0b", false)?, ["a\0b"]);
+    Ok(())
+}
+
+#[test]
+fn nul_separated() -> crate::Result {
+    assert!(lines("", true)?.is_empty());
+    assert_eq!(
+        lines("a\0\"b\"\0c\nd\r\0", true)?,
+        ["a", "\"b\"", "c\nd\r"],
+        "everything is verbatim"
+    );
+    assert_eq!(lines("a\0", true)?, ["a"], "a trailing NUL is optional");
+    Ok(())
+}
+
+#[test]
+fn badly_quoted_lines_are_an_error() {
+    let err = |input: &str| {
+        split(input.as_bytes(), false)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(err("a\n\"\\q\""), "Line 2 is badly quoted");
+    assert_eq!(
+        err("\"unterminated"),
+        "Line 1 starts with a quote, but misses the closing quote"
+    );
+    assert_eq!(
+        err("\"escaped\\\""),
+        "Line 1 starts with a quote, but misses the closing quote"
+    );
+}
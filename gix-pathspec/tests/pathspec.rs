@@ -1,5 +1,6 @@
 pub use gix_testtools::Result;
 
+mod file;
 mod normalize;
 mod parse;
 mod search;
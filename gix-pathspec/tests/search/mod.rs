@@ -402,6 +402,324 @@ fn simplified_search_handles_nil() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn search_modes_from_magic_signatures_are_used_for_matching() -> crate::Result {
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&[":(literal)*.o"]), None, Path::new(""))?;
+    assert!(
+        search
+            .pattern_matching_relative_path("*.o".into(), Some(false), &mut no_attrs)
+            .is_some(),
+        "literal patterns match verbatim"
+    );
+    assert!(
+        search
+            .pattern_matching_relative_path("a.o".into(), Some(false), &mut no_attrs)
+            .is_none(),
+        "wildcards have no special meaning"
+    );
+
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&[":(glob)dir/*"]), None, Path::new(""))?;
+    assert!(search
+        .pattern_matching_relative_path("dir/a".into(), Some(false), &mut no_attrs)
+        .is_some());
+    assert!(
+        search
+            .pattern_matching_relative_path("dir/sub/a".into(), Some(false), &mut no_attrs)
+            .is_none(),
+        "a single star doesn't match slashes in glob mode"
+    );
+
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&[":(glob)dir/**/a"]), None, Path::new(""))?;
+    for path in ["dir/a", "dir/sub/a", "dir/sub/sub/a"] {
+        assert!(
+            search
+                .pattern_matching_relative_path(path.into(), Some(false), &mut no_attrs)
+                .is_some(),
+            "{path}: double-stars match any amount of directories"
+        );
+    }
+
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&["dir/*"]), None, Path::new(""))?;
+    assert!(
+        search
+            .pattern_matching_relative_path("dir/sub/a".into(), Some(false), &mut no_attrs)
+            .is_some(),
+        "by default, a single star also matches slashes"
+    );
+
+    assert!(
+        matches!(
+            gix_pathspec::parse(b":(glob,unknown)dir", Default::default()),
+            Err(gix_pathspec::parse::Error::InvalidKeyword { keyword }) if keyword == "unknown"
+        ),
+        "unknown magic words are never dropped silently"
+    );
+    Ok(())
+}
+
+#[test]
+fn literal_and_glob_matching_matrix() -> crate::Result {
+    for (spec, path, glob, literal) in [
+        ("a*", "a*", Some(WildcardMatch), Some(Verbatim)),
+        ("a*", "ab", Some(WildcardMatch), None),
+        ("a*", "a*/file", Some(WildcardMatch), Some(Prefix)),
+        ("a*", "ab/file", Some(WildcardMatch), None),
+        ("a*", "b", None, None),
+        ("a?", "a?", Some(WildcardMatch), Some(Verbatim)),
+        ("a?", "ab", Some(WildcardMatch), None),
+        ("a[bc]", "a[bc]", Some(Verbatim), Some(Verbatim)),
+        ("a[bc]", "ac", Some(WildcardMatch), None),
+        ("a\\*", "a\\*", Some(Verbatim), Some(Verbatim)),
+        ("a\\*", "a\\b", None, None),
+        ("dir/*.o", "dir/*.o", Some(WildcardMatch), Some(Verbatim)),
+        ("dir/*.o", "dir/a.o", Some(WildcardMatch), None),
+    ] {
+        for (spec, expected) in [(spec.to_owned(), glob), (format!(":(literal){spec}"), literal)] {
+            let mut search = gix_pathspec::Search::from_specs(pathspecs(&[&spec]), None, Path::new(""))?;
+            assert_eq!(
+                search
+                    .pattern_matching_relative_path(path.into(), Some(false), &mut no_attrs)
+                    .map(|m| m.kind),
+                expected,
+                "{spec} for {path}"
+            );
+        }
+    }
+
+    let search = gix_pathspec::Search::from_specs(pathspecs(&["a*/file"]), None, Path::new(""))?;
+    assert_eq!(search.common_prefix(), "a");
+    assert!(search.can_match_relative_path("ab".into(), Some(true)));
+    assert!(search.directory_matches_prefix("ab".into(), false));
+
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&[":(literal)a*/file"]), None, Path::new(""))?;
+    assert_eq!(
+        search.common_prefix(),
+        "a*/file",
+        "the whole literal path is used as prefix"
+    );
+    assert!(
+        !search.can_match_relative_path("ab".into(), Some(true)),
+        "literal stars don't match anything but themselves, so nothing in this directory can match"
+    );
+    assert!(search.can_match_relative_path("a*".into(), Some(true)));
+    assert!(!search.directory_matches_prefix("ab".into(), true));
+    assert!(search.directory_matches_prefix("a*".into(), true));
+    assert_eq!(
+        search
+            .pattern_matching_relative_path("a*/file".into(), Some(false), &mut no_attrs)
+            .map(|m| m.kind),
+        Some(Verbatim)
+    );
+    assert!(search
+        .pattern_matching_relative_path("ab/file".into(), Some(false), &mut no_attrs)
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn paths_with_newlines_and_non_utf8_bytes_are_matched_byte_by_byte() -> crate::Result {
+    let search_for = |spec: &[u8]| {
+        gix_pathspec::Search::from_specs(
+            Some(gix_pathspec::parse(spec, Default::default()).expect("valid")),
+            None,
+            Path::new(""),
+        )
+    };
+    let is_match = |search: &mut gix_pathspec::Search, path: &[u8]| {
+        search
+            .pattern_matching_relative_path(path.into(), Some(false), &mut no_attrs)
+            .is_some()
+    };
+
+    let mut search = search_for(b":(literal)dir/a\nb\xff")?;
+    assert!(is_match(&mut search, b"dir/a\nb\xff"), "the full path matches verbatim");
+    for path in [&b"dir/a"[..], b"dir/a\nb", b"dir/a\nb\xfe", b"dir/a\rb\xff"] {
+        assert!(
+            !is_match(&mut search, path),
+            "{:?}: nothing is truncated at the newline and no byte is replaced",
+            BStr::new(path)
+        );
+    }
+
+    let mut search = search_for(b"dir/a\n*")?;
+    assert!(is_match(&mut search, b"dir/a\nb\xff"), "wildcards match any byte");
+    assert!(!is_match(&mut search, b"dir/ab\xff"));
+
+    let mut search = search_for(b"dir/a?b\xff")?;
+    assert!(
+        is_match(&mut search, b"dir/a\nb\xff"),
+        "a newline is a single character to `?`"
+    );
+    Ok(())
+}
+
+#[test]
+fn repeated_matches_in_changing_directories_are_consistent() -> crate::Result {
+    let specs = [
+        "a/b/*.rs",
+        ":(icase)A/C/file",
+        ":(exclude)a/b/ex*",
+        "a/bc",
+        "*.md",
+        ":(literal)a/b/lit*",
+    ];
+    let paths = [
+        "a/b/lib.rs",
+        "a/b/main.rs",
+        "a/b/excluded.rs",
+        "a/b/lit*",
+        "a/c/file",
+        "a/C/FILE",
+        "a/bc",
+        "a/bc/nested",
+        "a/b/c/deep.rs",
+        "readme.md",
+        "a/b/lib.rs",
+        "x/y/z.md",
+        "x/y/z.rs",
+        "a/c/file",
+    ];
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&specs), None, Path::new(""))?;
+    for path in paths {
+        let actual = search
+            .pattern_matching_relative_path(path.into(), Some(false), &mut no_attrs)
+            .map(|m| (m.sequence_number, m.kind));
+        let mut fresh_search = gix_pathspec::Search::from_specs(pathspecs(&specs), None, Path::new(""))?;
+        let expected = fresh_search
+            .pattern_matching_relative_path(path.into(), Some(false), &mut no_attrs)
+            .map(|m| (m.sequence_number, m.kind));
+        assert_eq!(
+            actual, expected,
+            "{path}: the outcome doesn't depend on previously matched paths"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn all_pattern_matches() -> crate::Result {
+    let mut search = gix_pathspec::Search::from_specs(
+        pathspecs(&["dir/", "dir/*.rs", ":(exclude)dir/gen*", "other"]),
+        None,
+        Path::new(""),
+    )?;
+    let mut all_matches = |path: &str| {
+        search
+            .all_pattern_matches(path.into(), Some(false), &mut no_attrs)
+            .into_iter()
+            .map(|m| (m.sequence_number, m.kind, m.is_excluded()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        all_matches("dir/generated.rs"),
+        [(2, WildcardMatch, true), (0, Prefix, false), (1, WildcardMatch, false)],
+        "the winning exclude comes first, followed by all other matches in order"
+    );
+    assert_eq!(
+        all_matches("dir/lib.rs"),
+        [(0, Prefix, false), (1, WildcardMatch, false)]
+    );
+    assert_eq!(all_matches("dir/readme"), [(0, Prefix, false)]);
+    assert_eq!(all_matches("readme"), [], "nothing matches");
+    assert_eq!(all_matches("outside"), [], "the common prefix doesn't match");
+
+    for path in ["dir/generated.rs", "dir/lib.rs", "dir/readme", "readme"] {
+        let first = search
+            .pattern_matching_relative_path(path.into(), Some(false), &mut no_attrs)
+            .map(|m| (m.sequence_number, m.kind));
+        let all = search.all_pattern_matches(path.into(), Some(false), &mut no_attrs);
+        assert_eq!(
+            first,
+            all.first().map(|m| (m.sequence_number, m.kind)),
+            "{path}: the first of all matches is the one that decides"
+        );
+    }
+
+    let mut search = gix_pathspec::Search::from_specs(pathspecs(&[":!*.o", ":!dir/"]), None, Path::new(""))?;
+    let actual: Vec<_> = search
+        .all_pattern_matches("dir/a.o".into(), Some(false), &mut no_attrs)
+        .into_iter()
+        .map(|m| (m.sequence_number, m.kind, m.is_excluded()))
+        .collect();
+    assert_eq!(
+        actual,
+        [(0, WildcardMatch, true), (1, Prefix, true), (2, Always, false)],
+        "if all patterns are excluded, the implied pattern that includes everything comes last"
+    );
+    Ok(())
+}
+
+#[test]
+fn intersect() -> crate::Result {
+    let is_included = |search: &mut gix_pathspec::Search, path: &str, is_dir: bool| {
+        search
+            .pattern_matching_relative_path(path.into(), Some(is_dir), &mut no_attrs)
+            .is_some_and(|m| !m.is_excluded())
+    };
+    let search = |specs: &[&str]| gix_pathspec::Search::from_specs(pathspecs(specs), None, Path::new(""));
+
+    let mut actual = search(&["src/"])?
+        .intersect(&search(&["src/lib.rs"])?)
+        .expect("overlap");
+    assert!(is_included(&mut actual, "src/lib.rs", false));
+    assert!(!is_included(&mut actual, "src/main.rs", false));
+    assert!(!is_included(&mut actual, "lib.rs", false));
+    assert_eq!(actual.common_prefix(), "src/lib.rs");
+
+    let mut actual = search(&["src/lib.rs"])?
+        .intersect(&search(&["src/"])?)
+        .expect("order doesn't matter");
+    assert_eq!(actual.patterns().count(), 1);
+    assert!(is_included(&mut actual, "src/lib.rs", false));
+
+    let mut actual = search(&["src", "tests/*.rs", ":!src/generated"])?
+        .intersect(&search(&["src/a", "tests/", ":!src/a/b"])?)
+        .expect("overlap");
+    assert!(is_included(&mut actual, "src/a/c", false));
+    assert!(is_included(&mut actual, "tests/c.rs", false));
+    assert!(!is_included(&mut actual, "src/a/b", false), "excludes of both are kept");
+    assert!(!is_included(&mut actual, "src/generated", false));
+    assert!(!is_included(&mut actual, "src/b", false), "not in other");
+    assert!(!is_included(&mut actual, "tests/c.sh", false), "not in self");
+
+    let mut actual = search(&["dir"])?.intersect(&search(&["dir/"])?).expect("same path");
+    assert!(is_included(&mut actual, "dir", true));
+    assert!(
+        !is_included(&mut actual, "dir", false),
+        "the directory requirement is kept"
+    );
+
+    for (lhs, rhs) in [(":(literal)src*", "src*"), ("src*", ":(literal)src*")] {
+        let mut actual = search(&[lhs])?.intersect(&search(&[rhs])?).expect("same path");
+        assert!(is_included(&mut actual, "src*", false));
+        assert!(
+            !is_included(&mut actual, "srcfoo", false),
+            "{lhs} and {rhs}: the literal pattern is kept as it matches less"
+        );
+    }
+
+    let mut actual = search(&[":!*.o"])?
+        .intersect(&search(&["src"])?)
+        .expect("all excluded matches everything else");
+    assert!(is_included(&mut actual, "src/a", false));
+    assert!(!is_included(&mut actual, "src/a.o", false));
+    assert!(!is_included(&mut actual, "a", false));
+
+    assert!(
+        search(&["src"])?.intersect(&search(&["tests"])?).is_none(),
+        "disjoint paths have no intersection"
+    );
+    assert!(
+        search(&["srcdir"])?.intersect(&search(&["src"])?).is_none(),
+        "directory boundaries are respected"
+    );
+    assert!(
+        search(&["*.rs"])?.intersect(&search(&["src"])?).is_none(),
+        "overlapping wildcards can't be intersected, so we are conservative"
+    );
+    Ok(())
+}
+
 #[test]
 fn longest_common_directory_no_prefix() -> crate::Result {
     let search = gix_pathspec::Search::from_specs(pathspecs(&["tests/a/", "tests/b/", ":!*.sh"]), None, Path::new(""))?;
@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gix_object::bstr::ByteSlice;
+
+fn describe(c: &mut Criterion) {
+    let store = gix_odb::at(
+        gix_testtools::scripted_fixture_read_only("make_repo_with_branches.sh")
+            .unwrap()
+            .join(".git/objects"),
+    )
+    .unwrap();
+    let commit_id = hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b");
+    let name_by_oid: gix_hashtable::HashMap<_, _> = vec![
+        (
+            hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+            Cow::Borrowed(b"at-c5".as_bstr()),
+        ),
+        (
+            hex_to_id("9152eeee2328073cf23dcf8e90c949170b711659"),
+            b"at-b1c1".as_bstr().into(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    c.bench_function("describe()", |b| {
+        b.iter(|| {
+            let mut graph = gix_revision::Graph::new(&store, None);
+            black_box(gix_revision::describe(
+                &commit_id,
+                &mut graph,
+                gix_revision::describe::Options {
+                    name_by_oid: name_by_oid.clone(),
+                    ..Default::default()
+                },
+            ))
+            .unwrap()
+            .expect("found")
+            .depth
+        })
+    });
+
+    let mut graph = gix_revision::Graph::new(&store, None);
+    c.bench_function("describe::name_and_depth()", |b| {
+        b.iter(|| {
+            black_box(gix_revision::describe::name_and_depth(
+                &commit_id,
+                &mut graph,
+                &name_by_oid,
+                10,
+                false,
+            ))
+            .unwrap()
+            .expect("found")
+            .1
+        })
+    });
+
+    let commits: Vec<_> = gix_revision::Graph::<()>::new(&store, None)
+        .reachable_from(Some(commit_id), &[])
+        .unwrap()
+        .into_iter()
+        .collect();
+    c.bench_function("describe::name_and_depth() for all commits", |b| {
+        let mut graph = gix_revision::Graph::new(&store, None);
+        b.iter(|| {
+            for commit in &commits {
+                black_box(gix_revision::describe::name_and_depth(commit, &mut graph, &name_by_oid, 10, false).unwrap());
+            }
+        })
+    });
+    c.bench_function("describe::nearest_names() for all commits", |b| {
+        let mut graph = gix_revision::Graph::new(&store, None);
+        b.iter(|| {
+            black_box(gix_revision::describe::nearest_names(&commits, &mut graph, &name_by_oid, 10, false).unwrap())
+                .len()
+        })
+    });
+}
+
+criterion_group!(benches, describe);
+criterion_main!(benches);
+
+fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
+    gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
+}
@@ -3,9 +3,11 @@ use std::{
     fmt::{Display, Formatter},
 };
 
-use bstr::BStr;
+use bstr::{BStr, BString};
 use gix_hashtable::HashMap;
 
+pub use function::{commits_since_name, contains, first_parent_count, name_and_depth, nearest_names};
+
 /// The positive result produced by [describe()][function::describe()].
 #[derive(Debug, Clone)]
 pub struct Outcome<'name> {
@@ -17,25 +19,79 @@ pub struct Outcome<'name> {
     pub id: gix_hash::ObjectId,
     /// The number of commits that are between the tag or branch with `name` and `id`.
     /// These commits are all in the future of the named tag or branch.
+    ///
+    /// It's 0 for [exact matches](Self::is_exact_match()), which is why it isn't displayed in the short form when formatted.
     pub depth: u32,
     /// The mapping between object ids and their names initially provided by the describe call.
     pub name_by_oid: HashMap<gix_hash::ObjectId, Cow<'name, BStr>>,
     /// The amount of commits we traversed.
     pub commits_seen: u32,
+    /// The amount of names that were found during traversal and considered as candidates, which is at most
+    /// the effective [`max_candidates`](Options::max_candidates).
+    ///
+    /// If it's equal to it, the traversal may have stopped before seeing all names, so the chosen name may not be
+    /// the closest one.
+    pub candidates_seen: usize,
+    /// If `Some(suffix)`, the caller determined the working tree to be dirty and `suffix` will be appended when formatting.
+    pub dirty_suffix: Option<String>,
+    /// The separator to place between the name, the depth and the abbreviated hash when formatting.
+    pub separator: Cow<'static, str>,
+    /// If `Some(len)`, the abbreviated hash uses at least `len` hex characters when formatting, or is omitted if `len` is 0.
+    /// If `None`, the hex length passed to [`into_format()`](Self::into_format()) is used as is.
+    pub abbrev: Option<usize>,
+    /// If `true`, the long form is produced when formatting, even for [exact matches](Self::is_exact_match()).
+    pub long: bool,
 }
 
 impl<'a> Outcome<'a> {
+    /// Return true if a `name` was found that is directly associated with `id`, i.e. there are no commits between them
+    /// and [`depth`](Self::depth) is 0.
+    pub fn is_exact_match(&self) -> bool {
+        self.name.is_some() && self.depth == 0
+    }
+
     /// Turn this outcome into a structure that can display itself in the typical `git describe` format.
+    ///
+    /// `hex_len` is the amount of hex characters needed to keep the abbreviated `id` unambiguous, which is used unless
+    /// [`abbrev`](Self::abbrev) asks for more characters, or for no hash at all.
     pub fn into_format(self, hex_len: usize) -> Format<'a> {
+        let hex_len = match self.abbrev {
+            Some(0) => 0,
+            Some(abbrev) => abbrev.max(hex_len),
+            None => hex_len,
+        };
         Format {
             name: self.name,
             id: self.id,
             hex_len,
             depth: self.depth,
-            long: false,
-            dirty_suffix: None,
+            long: self.long,
+            dirty_suffix: self.dirty_suffix,
+            separator: self.separator,
         }
     }
+
+    /// Like [`into_format()`][Self::into_format()], but use as many hex characters as needed to make the abbreviated `id`
+    /// unique among all commits in `commit_graph`, while using at least `min_hex_len` characters.
+    ///
+    /// Note that uniqueness is only guaranteed among commits in `commit_graph`, so other objects or commits that were created
+    /// after it was written may share the abbreviation. If `id` isn't in `commit_graph`, `min_hex_len` is used.
+    pub fn into_format_unique_in(self, min_hex_len: usize, commit_graph: &gix_commitgraph::Graph) -> Format<'a> {
+        let hex_len = commit_graph
+            .unique_hex_len(self.id)
+            .map_or(min_hex_len, |len| len.max(min_hex_len));
+        self.into_format(hex_len)
+    }
+}
+
+/// The result of [`commits_since_name()`][function::commits_since_name()].
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+pub struct CommitsSinceName<'a> {
+    /// The name closest to the commit, like the tag of the last release, or `None` if no name is reachable from it.
+    pub name: Option<&'a BStr>,
+    /// The amount of commits reachable from the commit that aren't reachable from `name`, or the amount of all commits
+    /// reachable from the commit, including itself, if there is no `name`.
+    pub count: u32,
 }
 
 /// A structure implementing `Display`, producing a `git describe` like string.
@@ -48,6 +104,8 @@ pub struct Format<'a> {
     /// The `id` of the commit to describe.
     pub id: gix_hash::ObjectId,
     /// The amount of hex characters to use to display `id`.
+    ///
+    /// If 0, no abbreviated `id` is displayed after `name`, and if there is no `name`, the full `id` is displayed instead.
     pub hex_len: usize,
     /// The amount of commits between `name` and `id`, where `id` is in the future of `name`.
     pub depth: u32,
@@ -57,6 +115,10 @@ pub struct Format<'a> {
     /// If `Some(suffix)`, it will be appended to the describe string.
     /// This should be set if the working tree was determined to be dirty.
     pub dirty_suffix: Option<String>,
+    /// The separator between `name`, `depth` and the abbreviated `id` in the long form, like `-` in `v1.0-3-gabc123`.
+    ///
+    /// Note that the `dirty_suffix` is always separated with `-`.
+    pub separator: Cow<'static, str>,
 }
 
 impl<'a> Format<'a> {
@@ -65,6 +127,15 @@ impl<'a> Format<'a> {
         self.depth == 0
     }
 
+    /// Return true if the long form `<name>-<depth>-g<hash>` is produced when displaying this instance, which is the case if
+    /// there is a `name` and `id` isn't an [exact match](Self::is_exact_match()) or if [`long`](Self::long) is set.
+    ///
+    /// If there is no `name`, only the abbreviated `id` is displayed, which isn't considered the long form.
+    /// With a [`hex_len`](Self::hex_len) of 0, only the `name` is displayed.
+    pub fn is_long_form(&self) -> bool {
+        self.name.is_some() && self.hex_len != 0 && (self.long || !self.is_exact_match())
+    }
+
     /// Set this instance to print in long mode, that is if `depth` is 0, it will still print the whole
     /// long form even though it's not quite necessary.
     ///
@@ -78,11 +149,19 @@ impl<'a> Format<'a> {
 impl<'a> Display for Format<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some(name) = self.name.as_deref() {
-            if !self.long && self.is_exact_match() {
+            if !self.is_long_form() {
                 name.fmt(f)?;
             } else {
-                write!(f, "{}-{}-g{}", name, self.depth, self.id.to_hex_with_len(self.hex_len))?;
+                write!(
+                    f,
+                    "{name}{sep}{depth}{sep}g{hex}",
+                    sep = self.separator,
+                    depth = self.depth,
+                    hex = self.id.to_hex_with_len(self.hex_len)
+                )?;
             }
+        } else if self.hex_len == 0 {
+            self.id.to_hex().fmt(f)?;
         } else {
             self.id.to_hex_with_len(self.hex_len).fmt(f)?;
         }
@@ -104,16 +183,68 @@ pub struct Options<'name> {
     /// The candidate names from which to determine the `name` to use for the describe string,
     /// as a mapping from a commit id and the name associated with it.
     pub name_by_oid: HashMap<gix_hash::ObjectId, Cow<'name, BStr>>,
-    /// The amount of names we will keep track of. Defaults to the maximum of 32.
+    /// The ids of names in `name_by_oid` in order of preference, to deterministically pick one of multiple candidates
+    /// that are at the same depth, like tags in semantic version order. Default: empty.
+    ///
+    /// Candidates listed earlier win over those listed later, and listed candidates win over those that aren't listed.
+    /// Candidates that aren't listed are picked in the order in which they were found during traversal, which
+    /// is also what happens if this list is empty.
+    pub name_order: Vec<gix_hash::ObjectId>,
+    /// The amount of names we will keep track of, similar to `git describe --candidates=<n>`. Default: 10.
     ///
-    /// If the number is exceeded, it will be capped at 32 and defaults to 10.
+    /// Once this many names were found, the traversal stops looking for more and only finishes the depth computation of
+    /// the best one, which bounds the traversal cost at the expense of accuracy. Values larger than 32 are capped at 32,
+    /// and with 0, only exact matches are used. [`Outcome::candidates_seen`] tells how many names were actually considered.
     pub max_candidates: usize,
     /// If no candidate for naming, always show the abbreviated hash. Default: false.
     pub fallback_to_oid: bool,
     /// Only follow the first parent during graph traversal. Default: false.
     ///
-    /// This may speed up the traversal at the cost of accuracy.
+    /// This may speed up the traversal at the cost of accuracy. As a result, the name is the most recent one along the
+    /// first-parent chain, ignoring names on merged branches even if they are closer, and the depth is the amount of commits
+    /// along the first-parent chain between it and the described commit.
     pub first_parent: bool,
+    /// If `true`, candidates are still found by following all parents, but the depth of the chosen name is the amount of commits
+    /// along the first-parent chain between it and the described commit. Names that aren't on the first-parent chain,
+    /// like tags on merged side branches, keep their regular depth. Default: false.
+    ///
+    /// This has no effect if `first_parent` is `true` as the depth is counted along the first-parent chain already.
+    pub depth_first_parent_only: bool,
+    /// If `Some(max)`, names that are more than `max` commits away from the described commit aren't used,
+    /// to avoid describing commits in terms of ancient tags. Default: `None`.
+    ///
+    /// If no name qualifies, the abbreviated hash is used if `fallback_to_oid` is `true`, or no outcome is produced.
+    pub max_depth_for_candidates: Option<u32>,
+    /// If `true`, the caller determined the working tree to be dirty, which causes `dirty_suffix` to be appended
+    /// to the describe string, even if the commit is an exact match of a name. Default: false.
+    ///
+    /// Note that determining whether the worktree is dirty is up to the caller. If that failed, like `git describe --broken`
+    /// does, set this to `true` along with a `dirty_suffix` of `broken`.
+    pub dirty: bool,
+    /// The suffix to append to the describe string if `dirty` is `true`, without the leading `-`. Default: `dirty`.
+    pub dirty_suffix: Cow<'static, str>,
+    /// The separator to place between the name, the depth and the abbreviated hash, like `.` to produce `v1.0.3.gabc123`.
+    /// Default: `-`.
+    pub separator: Cow<'static, str>,
+    /// If `Some(len)`, use at least `len` hex characters for the abbreviated hash, similar to `git describe --abbrev=<len>`.
+    /// `Some(0)` omits the `-<depth>-g<hash>` suffix entirely, and makes the full hash the fallback if there is no name.
+    /// Default: `None`, to use the amount of characters passed to [`Outcome::into_format()`], which should keep the hash
+    /// unambiguous.
+    pub abbrev: Option<usize>,
+    /// If `true`, always produce the long form `<name>-<depth>-g<hash>` even if the commit is an exact match of a name,
+    /// similar to `git describe --long`. Default: false.
+    ///
+    /// This has no effect if `abbrev` is `Some(0)`.
+    pub long: bool,
+    /// Glob patterns like `v*` of which at least one must match a name in `name_by_oid` for it to be a candidate,
+    /// similar to `git describe --match`. All names are candidates if this is empty. Default: empty.
+    ///
+    /// Patterns are matched against the names as they are provided, with `*` also matching `/`.
+    pub match_patterns: Vec<BString>,
+    /// Glob patterns like `*-rc*` which, if one of them matches a name in `name_by_oid`, prevent it from being a candidate,
+    /// similar to `git describe --exclude`. Exclusions apply even if a name also matches one of the `match_patterns`.
+    /// Default: empty.
+    pub exclude_patterns: Vec<BString>,
 }
 
 impl<'name> Default for Options<'name> {
@@ -121,8 +252,18 @@ impl<'name> Default for Options<'name> {
         Options {
             max_candidates: 10, // the same number as git uses, otherwise we perform worse by default on big repos
             name_by_oid: Default::default(),
+            name_order: Vec::new(),
             fallback_to_oid: false,
             first_parent: false,
+            depth_first_parent_only: false,
+            max_depth_for_candidates: None,
+            dirty: false,
+            dirty_suffix: "dirty".into(),
+            separator: "-".into(),
+            match_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            abbrev: None,
+            long: false,
         }
     }
 }
@@ -139,17 +280,24 @@ pub enum Error {
     },
     #[error("A commit could not be decoded during traversal")]
     Decode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Lookup(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    Parent(#[from] crate::graph::commit::iter_parents::Error),
 }
 
 pub(crate) mod function {
-    use std::{borrow::Cow, cmp::Ordering};
+    use std::{borrow::Cow, cmp::Ordering, rc::Rc};
 
-    use bstr::BStr;
-    use gix_hash::oid;
+    use bstr::{BStr, BString};
+    use gix_date::SecondsSinceUnixEpoch;
+    use gix_hash::{oid, ObjectId};
+    use gix_hashtable::HashMap;
 
-    use super::{Error, Outcome};
+    use super::{CommitsSinceName, Error, Outcome};
     use crate::{
         describe::{CommitTime, Flags, Options, MAX_CANDIDATES},
+        graph::lookup_parents,
         Graph, PriorityQueue,
     };
 
@@ -157,15 +305,26 @@ pub(crate) mod function {
     /// an `Outcome`, which converted [`into_format()`][Outcome::into_format()] will produce a typical `git describe` string.
     ///
     /// Note that the `name_by_oid` map is returned in the [`Outcome`], which can be forcefully returned even if there was no matching
-    /// candidate by setting `fallback_to_oid` to true.
+    /// candidate by setting `fallback_to_oid` to true. It only contains the names that passed the `match_patterns` and
+    /// `exclude_patterns` filters.
     pub fn describe<'name>(
         commit: &oid,
         graph: &mut Graph<'_, Flags>,
         Options {
-            name_by_oid,
-            mut max_candidates,
+            mut name_by_oid,
+            name_order,
+            max_candidates,
             fallback_to_oid,
             first_parent,
+            depth_first_parent_only,
+            max_depth_for_candidates,
+            dirty,
+            dirty_suffix,
+            separator,
+            match_patterns,
+            exclude_patterns,
+            abbrev,
+            long,
         }: Options<'name>,
     ) -> Result<Option<Outcome<'name>>, Error> {
         let _span = gix_trace::coarse!(
@@ -175,29 +334,577 @@ pub(crate) mod function {
             max_candidates,
             first_parent
         );
-        max_candidates = max_candidates.min(MAX_CANDIDATES);
-        if let Some(name) = name_by_oid.get(commit) {
-            return Ok(Some(Outcome {
-                name: name.clone().into(),
-                id: commit.to_owned(),
-                depth: 0,
-                name_by_oid,
-                commits_seen: 0,
-            }));
+        let dirty_suffix = dirty.then(|| dirty_suffix.into_owned());
+        if !match_patterns.is_empty() || !exclude_patterns.is_empty() {
+            name_by_oid.retain(|_, name| is_candidate_name(name.as_ref(), &match_patterns, &exclude_patterns));
+        }
+        let (found, commits_seen) = find_name(commit, graph, &name_by_oid, &name_order, max_candidates, first_parent)?;
+        let candidates_seen = found.as_ref().map_or(0, |found| found.candidates_seen);
+        let found = match found {
+            Some(found) => {
+                let depth = if depth_first_parent_only && !first_parent {
+                    first_parent_depth(graph, commit, &found)?.unwrap_or(found.depth)
+                } else {
+                    found.depth
+                };
+                Some((found.name.clone(), depth))
+                    .filter(|(_, depth)| max_depth_for_candidates.map_or(true, |max| *depth <= max))
+            }
+            None => None,
+        };
+        let (name, depth) = match found {
+            Some((name, depth)) => (Some(name), depth),
+            None if fallback_to_oid => (None, 0),
+            None => return Ok(None),
+        };
+        Ok(Some(Outcome {
+            name,
+            id: commit.to_owned(),
+            depth,
+            name_by_oid,
+            commits_seen,
+            candidates_seen,
+            dirty_suffix,
+            separator,
+            abbrev,
+            long,
+        }))
+    }
+
+    /// Return the name in `name_by_oid` whose history contains `commit`, followed by the path from it to `commit`,
+    /// like `v1.0~2^2~1`, similar to `git describe --contains`, or `None` if no name can reach `commit`.
+    ///
+    /// In the path, `~<n>` stands for the `n`th ancestor along the first parent, and `^<n>` for the `n`th parent of a merge.
+    /// If multiple names contain `commit`, the one pointing to the oldest commit wins, to name the earliest release that
+    /// contains it. Among names of the same age, the one with the shortest path is used.
+    ///
+    /// Just like `git`, commits that are more than a day older than `commit` aren't traversed to keep the traversal short,
+    /// which can cause names to be missed in histories with skewed commit dates.
+    pub fn contains(
+        commit: &oid,
+        graph: &mut Graph<'_, Flags>,
+        name_by_oid: &HashMap<ObjectId, Cow<'_, BStr>>,
+    ) -> Result<Option<BString>, Error> {
+        let cutoff = match graph.try_lookup(commit)? {
+            Some(commit) => commit.committer_timestamp()?.saturating_sub(CUTOFF_DATE_SLOP),
+            None => return Ok(None),
+        };
+        let mut tips = Vec::with_capacity(name_by_oid.len());
+        for (id, name) in name_by_oid {
+            if let Some(tip) = graph.try_lookup(id)? {
+                tips.push((tip.committer_timestamp()?, *id, name.as_ref()));
+            }
         }
+        // Older tips first so that fewer names have to be replaced by better ones later.
+        tips.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2.cmp(b.2)));
 
+        let mut names = HashMap::<ObjectId, PathName>::default();
+        let mut stack = Vec::new();
+        for (tip_time, tip_id, tip_name) in tips {
+            stack.push((
+                tip_id,
+                PathName {
+                    tip_time,
+                    name: Rc::new(tip_name.to_owned()),
+                    generation: 0,
+                    distance: 0,
+                },
+            ));
+            while let Some((id, path)) = stack.pop() {
+                if matches!(names.get(&id), Some(existing) if !path.is_better_than(existing)) {
+                    continue;
+                }
+                let parents = match graph.try_lookup(&id)? {
+                    Some(commit) if commit.committer_timestamp()? >= cutoff => {
+                        commit.iter_parents().collect::<Result<Vec<_>, _>>()?
+                    }
+                    _ => continue,
+                };
+                for (parent_index, parent_id) in parents.into_iter().enumerate().rev() {
+                    let parent_path = if parent_index == 0 {
+                        PathName {
+                            generation: path.generation + 1,
+                            distance: path.distance + 1,
+                            ..path.clone()
+                        }
+                    } else {
+                        let mut name = path.to_path();
+                        name.extend_from_slice(format!("^{}", parent_index + 1).as_bytes());
+                        PathName {
+                            tip_time,
+                            name: Rc::new(name),
+                            generation: 0,
+                            distance: path.distance + MERGE_TRAVERSAL_WEIGHT,
+                        }
+                    };
+                    stack.push((parent_id, parent_path));
+                }
+                names.insert(id, path);
+            }
+        }
+        Ok(names.get(commit).map(PathName::to_path))
+    }
+
+    /// Commits this many seconds older than the commit to name aren't traversed by [`contains()`].
+    const CUTOFF_DATE_SLOP: SecondsSinceUnixEpoch = 24 * 60 * 60;
+    /// The distance added when following a parent of a merge that isn't the first one, to prefer first-parent paths.
+    const MERGE_TRAVERSAL_WEIGHT: u64 = 65535;
+
+    /// A way to reach a commit from a name, as used by [`contains()`].
+    #[derive(Clone)]
+    struct PathName {
+        /// The commit time of the commit that the name points to.
+        tip_time: SecondsSinceUnixEpoch,
+        /// The name along with the path to the closest merge, if there was one.
+        name: Rc<BString>,
+        /// The amount of first parents between `name` and the commit.
+        generation: u32,
+        distance: u64,
+    }
+
+    impl PathName {
+        fn is_better_than(&self, other: &PathName) -> bool {
+            self.tip_time < other.tip_time || (self.tip_time == other.tip_time && self.distance < other.distance)
+        }
+
+        fn to_path(&self) -> BString {
+            let mut path = self.name.as_ref().clone();
+            if self.generation != 0 {
+                path.extend_from_slice(format!("~{}", self.generation).as_bytes());
+            }
+            path
+        }
+    }
+
+    /// Return `true` if `name` matches one of `match_patterns`, if there are any, and none of `exclude_patterns`.
+    fn is_candidate_name(name: &BStr, match_patterns: &[BString], exclude_patterns: &[BString]) -> bool {
+        let matches =
+            |pattern: &BString| gix_glob::wildmatch(pattern.as_ref(), name, gix_glob::wildmatch::Mode::empty());
+        (match_patterns.is_empty() || match_patterns.iter().any(matches)) && !exclude_patterns.iter().any(matches)
+    }
+
+    /// Like [`describe()`], but for use in hot loops as it borrows `name_by_oid` and only returns the name closest to `commit`
+    /// along with the amount of commits between them, or `None` if no name could be found.
+    ///
+    /// `max_candidates` and `first_parent` have the same meaning as in [`Options`]. `graph` can be reused across calls
+    /// to avoid allocations, and it's expected that `commit` is the id of a commit, not of a tag pointing to it.
+    pub fn name_and_depth<'a>(
+        commit: &oid,
+        graph: &mut Graph<'_, Flags>,
+        name_by_oid: &'a HashMap<ObjectId, Cow<'_, BStr>>,
+        max_candidates: usize,
+        first_parent: bool,
+    ) -> Result<Option<(&'a BStr, u32)>, Error> {
+        Ok(
+            find_name(commit, graph, name_by_oid, &[], max_candidates, first_parent)?
+                .0
+                .map(|found| (found.name.as_ref(), found.depth)),
+        )
+    }
+
+    /// Return the amount of commits on the first-parent chain from `start` to the root commit, including both,
+    /// similar to `git rev-list --first-parent --count <start>`, which is useful for build numbers.
+    ///
+    /// Only the first parent of each commit is followed, so merged branches don't affect the count. In histories with
+    /// multiple roots, only the root at the end of the first-parent chain is reached.
+    /// Commits that aren't present, like in shallow clones, end the chain.
+    pub fn first_parent_count(graph: &mut Graph<'_, Flags>, start: &oid) -> Result<u32, Error> {
+        let mut count = 0;
+        let mut current = start.to_owned();
+        while let Some(commit) = graph.try_lookup(&current)? {
+            count += 1;
+            match commit.iter_parents().next().transpose()? {
+                Some(parent_id) => current = parent_id,
+                None => break,
+            }
+        }
+        Ok(count)
+    }
+
+    /// Like [`name_and_depth()`], but for many `commits` at once, which are traversed together so that history they have in
+    /// common is visited only once instead of once per commit. This is much cheaper than describing each commit individually.
+    ///
+    /// Return a map from each of the `commits` to the name closest to it and its depth, without commits for which
+    /// no name could be found. `max_candidates` and `first_parent` have the same meaning as in [`Options`],
+    /// and `graph` is only used to look up commits.
+    pub fn nearest_names<'a>(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, Flags>,
+        name_by_oid: &'a HashMap<ObjectId, Cow<'_, BStr>>,
+        max_candidates: usize,
+        first_parent: bool,
+    ) -> Result<HashMap<ObjectId, (&'a BStr, u32)>, Error> {
+        let max_candidates = max_candidates.min(MAX_CANDIDATES);
+        let mut out = HashMap::default();
+        let mut starts = Vec::new();
+        for id in commits {
+            match name_by_oid.get(id) {
+                Some(name) => {
+                    out.insert(*id, (name.as_ref(), 0));
+                }
+                None if !starts.contains(id) => starts.push(*id),
+                None => {}
+            }
+        }
         if max_candidates == 0 || name_by_oid.is_empty() {
-            return if fallback_to_oid {
-                Ok(Some(Outcome {
-                    id: commit.to_owned(),
+            return Ok(out);
+        }
+        for starts in starts.chunks(StartFlags::BITS as usize) {
+            nearest_names_of_starts(starts, graph, name_by_oid, max_candidates, first_parent, &mut out)?;
+        }
+        Ok(out)
+    }
+
+    /// A bit-field which keeps track of which of the start commits of [`nearest_names()`] reach a commit.
+    type StartFlags = u64;
+
+    /// What's known about a commit while traversing for [`nearest_names()`].
+    #[derive(Default)]
+    struct Reach {
+        starts: StartFlags,
+        /// The start commits for which the commit was already visited, which can be fewer than `starts` if
+        /// it was reached by another start commit only after it was visited, for instance due to clock skew.
+        visited: StartFlags,
+        /// A bit-field with one bit for each candidate name that has the commit in its past.
+        ///
+        /// It's shared with the parents until it changes, which avoids copying it along linear history.
+        names: Rc<Vec<u64>>,
+        /// If `true`, the commit is currently in the queue.
+        queued: bool,
+    }
+
+    impl Reach {
+        fn has_name(&self, bit: usize) -> bool {
+            self.names.get(bit / 64).copied().unwrap_or_default() & (1 << (bit % 64)) != 0
+        }
+
+        fn add_name(&mut self, bit: usize) {
+            let names = Rc::make_mut(&mut self.names);
+            if names.len() <= bit / 64 {
+                names.resize(bit / 64 + 1, 0);
+            }
+            names[bit / 64] |= 1 << (bit % 64);
+        }
+
+        /// Add the start commits and names of `other` to ours, and return `true` if we were visited before
+        /// but there are new start commits for which we weren't visited yet.
+        fn merge(&mut self, other: &Reach) -> bool {
+            let unvisited_before = self.starts & !self.visited;
+            self.starts |= other.starts;
+            let has_new_names = !Rc::ptr_eq(&self.names, &other.names)
+                && other
+                    .names
+                    .iter()
+                    .enumerate()
+                    .any(|(idx, other_word)| other_word & !self.names.get(idx).copied().unwrap_or_default() != 0);
+            if has_new_names {
+                let names = Rc::make_mut(&mut self.names);
+                if names.len() < other.names.len() {
+                    names.resize(other.names.len(), 0);
+                }
+                for (word, other_word) in names.iter_mut().zip(other.names.iter()) {
+                    *word |= other_word;
+                }
+            }
+            self.visited != 0 && self.starts & !self.visited != unvisited_before
+        }
+
+        /// Count this queued commit as `pending` for all candidates of our start commits that aren't in its past,
+        /// or stop counting it if `queued` is `false`.
+        fn update_pending(&self, starts: &mut [Start<'_, '_>], queued: bool) {
+            for (idx, start) in starts.iter_mut().enumerate() {
+                if self.starts & (1 << idx) == 0 || start.done {
+                    continue;
+                }
+                for candidate in start.candidates.iter_mut().filter(|c| !self.has_name(c.name_bit)) {
+                    if queued {
+                        candidate.pending += 1;
+                    } else {
+                        candidate.pending -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The state of the search for the closest name of a single start commit in [`nearest_names()`].
+    struct Start<'a, 'name> {
+        id: ObjectId,
+        /// The candidate names in the order in which they were found.
+        candidates: Vec<NearestCandidate<'a, 'name>>,
+        commits_seen: u32,
+        /// If `true`, there were more candidates than we can track, so only the best one remains.
+        gave_up: bool,
+        /// If `true`, the depth of the best candidate is known and nothing changes anymore.
+        done: bool,
+    }
+
+    impl<'a, 'name> Start<'a, 'name> {
+        fn best_candidate(&self) -> Option<&NearestCandidate<'a, 'name>> {
+            self.candidates.iter().min_by_key(|c| c.depth)
+        }
+    }
+
+    struct NearestCandidate<'a, 'name> {
+        name: &'a Cow<'name, BStr>,
+        /// The bit identifying the candidate in [`Reach::names`], which is shared among all start commits.
+        name_bit: usize,
+        depth: u32,
+        /// The amount of queued commits reached by the start commit that don't have this candidate in their past.
+        pending: u32,
+    }
+
+    /// Traverse the past of all `start_ids` in a single walk ordered by commit date, just like [`find_name()`] does for one commit,
+    /// and put the closest name for each of them into `out`.
+    fn nearest_names_of_starts<'a>(
+        start_ids: &[ObjectId],
+        graph: &mut Graph<'_, Flags>,
+        name_by_oid: &'a HashMap<ObjectId, Cow<'_, BStr>>,
+        max_candidates: usize,
+        first_parent: bool,
+        out: &mut HashMap<ObjectId, (&'a BStr, u32)>,
+    ) -> Result<(), Error> {
+        let mut reach = HashMap::<ObjectId, Reach>::default();
+        let mut queue = PriorityQueue::new();
+        let mut starts = Vec::with_capacity(start_ids.len());
+        for (idx, id) in start_ids.iter().enumerate() {
+            let commit = graph.try_lookup(id)?;
+            if let Some(commit) = &commit {
+                queue.insert(commit.committer_timestamp()? as CommitTime, *id);
+                reach.insert(
+                    *id,
+                    Reach {
+                        starts: 1 << idx,
+                        queued: true,
+                        ..Default::default()
+                    },
+                );
+            }
+            starts.push(Start {
+                id: *id,
+                candidates: Vec::new(),
+                commits_seen: 0,
+                gave_up: false,
+                done: commit.is_none(),
+            });
+        }
+
+        let mut name_bit_by_id = HashMap::<ObjectId, usize>::default();
+        let mut active = Vec::new();
+        let mut parents = Vec::new();
+        while let Some(id) = queue.pop_value() {
+            // Take it out while we work with it so that the other commits remain accessible.
+            let mut commit_reach = reach.remove(&id).expect("queued commits are known");
+            commit_reach.queued = false;
+            commit_reach.update_pending(&mut starts, false);
+            active.clear();
+            let unvisited = commit_reach.starts & !commit_reach.visited;
+            active.extend((0..starts.len()).filter(|idx| unvisited & (1 << idx) != 0 && !starts[*idx].done));
+            if active.is_empty() {
+                reach.insert(id, commit_reach);
+                continue;
+            }
+
+            if let Some(name) = name_by_oid.get(&id) {
+                for idx in active.iter().copied() {
+                    let start = &mut starts[idx];
+                    if start.gave_up {
+                        continue;
+                    }
+                    if start.candidates.len() < max_candidates {
+                        let next_name_bit = name_bit_by_id.len();
+                        let name_bit = *name_bit_by_id.entry(id).or_insert(next_name_bit);
+                        commit_reach.add_name(name_bit);
+                        let pending = queue
+                            .iter_unordered()
+                            .filter(|id| {
+                                let r = &reach[*id];
+                                r.starts & (1 << idx) != 0 && !r.has_name(name_bit)
+                            })
+                            .count();
+                        start.candidates.push(NearestCandidate {
+                            name,
+                            name_bit,
+                            depth: start.commits_seen,
+                            pending: pending as u32,
+                        });
+                    } else {
+                        let best = start
+                            .candidates
+                            .iter()
+                            .enumerate()
+                            .min_by_key(|(_, c)| c.depth)
+                            .map(|(idx, _)| idx)
+                            .expect("at least one candidate");
+                        start.candidates.swap(0, best);
+                        start.candidates.truncate(1);
+                        start.gave_up = true;
+                    }
+                }
+            }
+
+            for idx in active.iter().copied() {
+                let start = &mut starts[idx];
+                start.commits_seen += 1;
+                for candidate in start
+                    .candidates
+                    .iter_mut()
+                    .filter(|c| !commit_reach.has_name(c.name_bit))
+                {
+                    candidate.depth += 1;
+                }
+            }
+            commit_reach.visited |= active.iter().fold(0, |flags, idx| flags | 1 << idx);
+
+            parents.clear();
+            if let Some(commit) = graph.try_lookup(&id)? {
+                for parent_id in commit.iter_parents() {
+                    parents.push(parent_id?);
+                    if first_parent {
+                        break;
+                    }
+                }
+            }
+            for parent_id in parents.drain(..) {
+                if let Some(parent_reach) = reach.get_mut(&parent_id) {
+                    if parent_reach.queued {
+                        parent_reach.update_pending(&mut starts, false);
+                        parent_reach.merge(&commit_reach);
+                        parent_reach.update_pending(&mut starts, true);
+                    } else if parent_reach.merge(&commit_reach) {
+                        // Visit it again for the start commits that reached it only after it was visited.
+                        if let Some(parent) = graph.try_lookup(&parent_id)? {
+                            queue.insert(parent.committer_timestamp()? as CommitTime, parent_id);
+                            parent_reach.queued = true;
+                            parent_reach.update_pending(&mut starts, true);
+                        }
+                    }
+                } else if let Some(parent) = graph.try_lookup(&parent_id)? {
+                    queue.insert(parent.committer_timestamp()? as CommitTime, parent_id);
+                    let parent_reach = Reach {
+                        starts: commit_reach.starts,
+                        visited: 0,
+                        names: Rc::clone(&commit_reach.names),
+                        queued: true,
+                    };
+                    parent_reach.update_pending(&mut starts, true);
+                    reach.insert(parent_id, parent_reach);
+                }
+            }
+
+            // A start is done once all of its commits that are left to visit are in the past of its best candidate,
+            // as these can't change the depth of the best candidate, and other candidates can only get deeper.
+            for idx in active.iter().copied() {
+                if matches!(starts[idx].best_candidate(), Some(best) if best.pending == 0 && commit_reach.has_name(best.name_bit))
+                {
+                    starts[idx].done = true;
+                }
+            }
+            reach.insert(id, commit_reach);
+            if starts.iter().all(|s| s.done) {
+                break;
+            }
+        }
+
+        out.extend(starts.iter().filter_map(|start| {
+            start
+                .best_candidate()
+                .map(|best| (start.id, (best.name.as_ref(), best.depth)))
+        }));
+        Ok(())
+    }
+
+    /// Return the name closest to `commit` along with the amount of commits that were added since, like the tag of the
+    /// last release and the amount of commits since that release, or the amount of all commits reachable from `commit`
+    /// if there is no name.
+    ///
+    /// `max_candidates` has the same meaning as in [`Options`], and `name_by_oid` would typically contain release tags.
+    pub fn commits_since_name<'a>(
+        commit: &oid,
+        graph: &mut Graph<'_, Flags>,
+        name_by_oid: &'a HashMap<ObjectId, Cow<'_, BStr>>,
+        max_candidates: usize,
+    ) -> Result<CommitsSinceName<'a>, Error> {
+        Ok(
+            match name_and_depth(commit, graph, name_by_oid, max_candidates, false)? {
+                Some((name, depth)) => CommitsSinceName {
+                    name: Some(name),
+                    count: depth,
+                },
+                None => CommitsSinceName {
                     name: None,
-                    name_by_oid,
+                    count: graph
+                        .reachable_from(Some(commit.to_owned()), &[])
+                        .map_err(|err| match err {
+                            lookup_parents::Error::Lookup(err) => Error::Lookup(err),
+                            lookup_parents::Error::Parent(err) => Error::Parent(err),
+                        })?
+                        .len() as u32,
+                },
+            },
+        )
+    }
+
+    /// A name that was found for a commit.
+    struct Found<'a, 'name> {
+        /// The id of the commit that is associated with `name`.
+        id: ObjectId,
+        name: &'a Cow<'name, BStr>,
+        /// The amount of commits in the future of `id`.
+        depth: u32,
+        /// The amount of candidates that were considered before choosing this one.
+        candidates_seen: usize,
+    }
+
+    /// Return the amount of commits along the first-parent chain from `commit` to the commit of `found`,
+    /// or `None` if it isn't on the first-parent chain.
+    ///
+    /// As the regular depth counts all commits in the future of `found`, only up to that many commits are traversed.
+    fn first_parent_depth(
+        graph: &mut Graph<'_, Flags>,
+        commit: &oid,
+        found: &Found<'_, '_>,
+    ) -> Result<Option<u32>, Error> {
+        let mut current = commit.to_owned();
+        for depth in 0..=found.depth {
+            if current == found.id {
+                return Ok(Some(depth));
+            }
+            match graph.try_lookup(&current)? {
+                Some(commit) => match commit.iter_parents().next().transpose()? {
+                    Some(parent_id) => current = parent_id,
+                    None => break,
+                },
+                None => break,
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return the name closest to `commit` along with its depth, if there is one, and the amount of commits seen.
+    fn find_name<'a, 'name>(
+        commit: &oid,
+        graph: &mut Graph<'_, Flags>,
+        name_by_oid: &'a HashMap<ObjectId, Cow<'name, BStr>>,
+        name_order: &[ObjectId],
+        mut max_candidates: usize,
+        first_parent: bool,
+    ) -> Result<(Option<Found<'a, 'name>>, u32), Error> {
+        max_candidates = max_candidates.min(MAX_CANDIDATES);
+        if let Some(name) = name_by_oid.get(commit) {
+            return Ok((
+                Some(Found {
+                    id: commit.to_owned(),
+                    name,
                     depth: 0,
-                    commits_seen: 0,
-                }))
-            } else {
-                Ok(None)
-            };
+                    candidates_seen: 1,
+                }),
+                0,
+            ));
+        }
+
+        if max_candidates == 0 || name_by_oid.is_empty() {
+            return Ok((None, 0));
         }
 
         let mut queue = PriorityQueue::from_iter(Some((u32::MAX, commit.to_owned())));
@@ -213,10 +920,12 @@ pub(crate) mod function {
                 if candidates.len() < max_candidates {
                     let identity_bit = 1 << candidates.len();
                     candidates.push(Candidate {
-                        name: name.clone(),
+                        id: commit,
+                        name,
                         commits_in_its_future: commits_seen - 1,
                         identity_bit,
                         order: candidates.len(),
+                        rank: name_order.iter().position(|id| *id == commit).unwrap_or(usize::MAX),
                     });
                     let flags = graph.get_mut(&commit).expect("inserted");
                     *flags |= identity_bit;
@@ -263,22 +972,13 @@ pub(crate) mod function {
         }
 
         if candidates.is_empty() {
-            return if fallback_to_oid {
-                Ok(Some(Outcome {
-                    id: commit.to_owned(),
-                    name: None,
-                    name_by_oid,
-                    depth: 0,
-                    commits_seen,
-                }))
-            } else {
-                Ok(None)
-            };
+            return Ok((None, commits_seen));
         }
 
         candidates.sort_by(|a, b| {
             a.commits_in_its_future
                 .cmp(&b.commits_in_its_future)
+                .then_with(|| a.rank.cmp(&b.rank))
                 .then_with(|| a.order.cmp(&b.order))
         });
 
@@ -294,13 +994,16 @@ pub(crate) mod function {
             first_parent,
         )?;
 
-        Ok(candidates.into_iter().next().map(|c| Outcome {
-            name: c.name.into(),
-            id: commit.to_owned(),
-            depth: c.commits_in_its_future,
-            name_by_oid,
+        let candidates_seen = candidates.len();
+        Ok((
+            candidates.into_iter().next().map(|c| Found {
+                id: c.id,
+                name: c.name,
+                depth: c.commits_in_its_future,
+                candidates_seen,
+            }),
             commits_seen,
-        }))
+        ))
     }
 
     fn parents_by_date_onto_queue_and_track_names(
@@ -327,7 +1030,7 @@ pub(crate) mod function {
     fn finish_depth_computation(
         mut queue: PriorityQueue<CommitTime, gix_hash::ObjectId>,
         graph: &mut Graph<'_, Flags>,
-        best_candidate: &mut Candidate<'_>,
+        best_candidate: &mut Candidate<'_, '_>,
         first_parent: bool,
     ) -> Result<u32, Error> {
         let mut commits_seen = 0;
@@ -351,13 +1054,16 @@ pub(crate) mod function {
     }
 
     #[derive(Debug)]
-    struct Candidate<'a> {
-        name: Cow<'a, BStr>,
+    struct Candidate<'a, 'name> {
+        id: ObjectId,
+        name: &'a Cow<'name, BStr>,
         commits_in_its_future: Flags,
         /// A single bit identifying this candidate uniquely in a bitset
         identity_bit: Flags,
         /// The order at which we found the candidate, first one has order = 0
         order: usize,
+        /// The position of the candidate in the caller-provided name order, or `usize::MAX` if it wasn't listed.
+        rank: usize,
     }
 }
 
@@ -18,5 +18,11 @@ pub use describe::function::describe;
 ///
 #[allow(clippy::empty_docs)]
 pub mod spec;
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "walk-with-changes")]
+pub mod walk_with_changes;
 pub use gix_revwalk::{graph, Graph, PriorityQueue};
 pub use spec::types::Spec;
+#[cfg(feature = "walk-with-changes")]
+pub use walk_with_changes::function::walk_with_changes;
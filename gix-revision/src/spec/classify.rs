@@ -0,0 +1,110 @@
+/// The kind of input as determined by [`classify()`][crate::spec::classify()].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// The input uses revision syntax or names a revision that can't be a path, like `HEAD~1`, `main^{tree}`, `refs/heads/main`
+    /// or a full hash, or it is a plain name that looks like a branch, like `main`.
+    Revision,
+    /// The input describes a set of commits with range syntax, like `a..b`, `a...b`, `a^@` or `a^!`.
+    Range,
+    /// The input can't be parsed as revision, or it looks like a path to a file, like `src/lib.rs` or `./file`.
+    Path,
+    /// The input is a name that could be a reference just as well as a path, like `feature/login` or `v1.0`,
+    /// so only a lookup in the repository and its worktree can tell.
+    Ambiguous,
+}
+
+/// Options for use in [`classify()`][crate::spec::classify()].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If set, inputs that would be [`Ambiguous`](Kind::Ambiguous) are classified as this kind instead.
+    ///
+    /// Use this to force the interpretation of tokens that could be a reference as well as a path, like `feature/login`,
+    /// so that they become a [`Revision`](Kind::Revision) even though a file of the same name may exist in the worktree.
+    /// As [`classify()`][crate::spec::classify()] never looks at the filesystem, this is based on the syntax alone,
+    /// and inputs that are unambiguous by their syntax are never affected.
+    pub ambiguous_as: Option<Kind>,
+}
+
+pub(crate) mod function {
+    use bstr::{BStr, ByteSlice};
+
+    use super::{Kind, Options};
+    use crate::{spec, spec::plan::Step};
+
+    /// Determine if `input` is a revision, a range of revisions or a path, without resolving anything
+    /// and by its syntax alone.
+    ///
+    /// Inputs that use revision syntax like navigation, peeling or reflog lookups are always revisions,
+    /// and everything that fails to parse as revision or contains bytes that are invalid in reference names is considered a path.
+    /// Plain names are classified heuristically: names without path separator and without `.` are revisions like `main`,
+    /// names with a path separator whose last component has a file extension are paths like `src/lib.rs`,
+    /// and everything else is [ambiguous](Kind::Ambiguous), unless [`Options::ambiguous_as`] is set.
+    pub fn classify(input: &BStr, Options { ambiguous_as }: Options) -> Kind {
+        match classify_by_syntax(input) {
+            Kind::Ambiguous => ambiguous_as.unwrap_or(Kind::Ambiguous),
+            unambiguous => unambiguous,
+        }
+    }
+
+    fn classify_by_syntax(input: &BStr) -> Kind {
+        if input.is_empty() || input.starts_with(b"./") || input.starts_with(b"../") || input.starts_with(b"/") {
+            return Kind::Path;
+        }
+        let steps = match crate::spec::plan(input) {
+            Ok(steps) => steps,
+            Err(_) => return Kind::Path,
+        };
+        if steps.iter().any(|step| {
+            matches!(
+                step,
+                Step::Kind(
+                    spec::Kind::RangeBetween
+                        | spec::Kind::ReachableToMergeBase
+                        | spec::Kind::IncludeReachableFromParents
+                        | spec::Kind::ExcludeReachableFromParents
+                )
+            )
+        }) {
+            return Kind::Range;
+        }
+        if !matches!(
+            steps.as_slice(),
+            [Step::ResolveRef(_) | Step::DisambiguatePrefix { .. }]
+        ) {
+            return Kind::Revision;
+        }
+
+        let name = input;
+        if name
+            .iter()
+            .any(|b| b.is_ascii_control() || matches!(b, b' ' | b'?' | b'*' | b'[' | b'\\'))
+        {
+            return Kind::Path;
+        }
+        if name.starts_with(b"refs/")
+            || (name.len() == gix_hash::Kind::longest().len_in_hex() && name.iter().all(u8::is_ascii_hexdigit))
+            || is_pseudo_ref(name)
+        {
+            return Kind::Revision;
+        }
+        let has_separator = name.contains(&b'/');
+        let last_component = name.rsplit_str(b"/").next().unwrap_or_default();
+        if !has_separator && !name.contains(&b'.') {
+            Kind::Revision
+        } else if has_separator && has_file_extension(last_component) {
+            Kind::Path
+        } else {
+            Kind::Ambiguous
+        }
+    }
+
+    fn is_pseudo_ref(name: &BStr) -> bool {
+        name.ends_with(b"HEAD") && name.iter().all(|b| b.is_ascii_uppercase() || *b == b'_')
+    }
+
+    fn has_file_extension(component: &[u8]) -> bool {
+        component
+            .rfind_byte(b'.')
+            .is_some_and(|pos| pos > 0 && pos + 1 < component.len())
+    }
+}
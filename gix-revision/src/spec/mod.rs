@@ -107,4 +107,14 @@ pub(crate) mod types {
 ///
 #[allow(clippy::empty_docs)]
 pub mod parse;
-pub use parse::function::parse;
+pub use parse::function::{parse, parse_many};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod classify;
+pub use classify::function::classify;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod plan;
+pub use plan::function::plan;
@@ -61,6 +61,19 @@ pub fn parse(mut input: &BStr, delegate: &mut impl Delegate) -> Result<(), Error
     }
 }
 
+/// Parse each revspec in `inputs` without stopping at the first invalid one, and return the errors of all that failed
+/// along with the index of their input, in order. An empty list means all inputs are valid.
+///
+/// As nothing is resolved, only syntax errors are reported, which is useful to validate many revspecs at once,
+/// like those in configuration files.
+pub fn parse_many<'a>(inputs: impl IntoIterator<Item = &'a BStr>) -> Vec<(usize, Error)> {
+    inputs
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, input)| spec::plan(input).err().map(|err| (idx, err)))
+        .collect()
+}
+
 mod intercept {
     use bstr::{BStr, BString};
 
@@ -335,6 +348,7 @@ where
         [b':', b'0', b':', path @ ..] => return consume_all(delegate.index_lookup(path.as_bstr(), 0)),
         [b':', b'1', b':', path @ ..] => return consume_all(delegate.index_lookup(path.as_bstr(), 1)),
         [b':', b'2', b':', path @ ..] => return consume_all(delegate.index_lookup(path.as_bstr(), 2)),
+        [b':', b'3', b':', path @ ..] => return consume_all(delegate.index_lookup(path.as_bstr(), 3)),
         [b':', path @ ..] => return consume_all(delegate.index_lookup(path.as_bstr(), 0)),
         _ => {}
     };
@@ -436,6 +450,8 @@ where
                 } else {
                     return Err(Error::ReflogLookupNeedsRefName { name: (*name).into() });
                 }
+            } else if is_signed_number(nav) {
+                return Err(Error::InvalidNumber { input: nav.into() });
             } else if let Some(kind) = SiblingBranch::parse(nav) {
                 if has_ref_or_implied_name {
                     delegate.sibling_branch(kind).ok_or(Error::Delegate)
@@ -599,6 +615,15 @@ fn parse_regex_prefix(regex: &BStr) -> Result<(&BStr, bool), Error> {
     })
 }
 
+/// Return `true` if `input` is a number with optional sign, no matter if it fits into an integer type.
+fn is_signed_number(input: &BStr) -> bool {
+    let digits = input
+        .strip_prefix(b"-")
+        .or_else(|| input.strip_prefix(b"+"))
+        .unwrap_or(input);
+    !digits.is_empty() && digits.iter().all(u8::is_ascii_digit)
+}
+
 fn try_parse_usize(input: &BStr) -> Result<Option<(usize, usize)>, Error> {
     let mut bytes = input.iter().peekable();
     if bytes.peek().filter(|&&&b| b == b'-' || b == b'+').is_some() {
@@ -0,0 +1,193 @@
+use bstr::BString;
+
+use crate::spec::parse::delegate;
+
+/// A single operation to perform to resolve a revision specification, as returned by [`plan()`][crate::spec::plan()].
+///
+/// Steps are to be performed in order, each one operating on the object produced by the previous one,
+/// with [`Kind`](Step::Kind) indicating that what follows is the second revision of a range.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub enum Step {
+    /// Resolve the reference with the given name, like `HEAD` or `main`, to obtain the starting point.
+    ResolveRef(BString),
+    /// Find the object whose id starts with `prefix` to obtain the starting point, using `hint` for disambiguation if set.
+    DisambiguatePrefix {
+        /// The prefix of the object to find.
+        prefix: gix_hash::Prefix,
+        /// Information to pick the right object if `prefix` is ambiguous.
+        hint: Option<PrefixHint>,
+    },
+    /// Lookup an entry in the reflog of the reference resolved previously, or of the current branch if there is none.
+    Reflog(delegate::ReflogLookup),
+    /// Obtain the branch that was checked out the given amount of checkouts ago, with 1 being the previous one.
+    NthCheckedOutBranch(usize),
+    /// Obtain the upstream or push branch of the branch resolved previously.
+    SiblingBranch(delegate::SiblingBranch),
+    /// Go to the nth parent or the nth ancestor of the current commit.
+    Traverse(delegate::Traversal),
+    /// Peel the current object to the given target.
+    PeelUntil(PeelTo),
+    /// Search for the youngest commit reachable from the current one, or from all references if there is none,
+    /// whose message matches `regex`.
+    SearchMessage {
+        /// The regular expression to match the commit message against.
+        regex: BString,
+        /// If `true`, the message must not match `regex`.
+        negated: bool,
+    },
+    /// Lookup the blob at `path` in the index at the given merge `stage`.
+    IndexLookup {
+        /// The path of the entry to lookup.
+        path: BString,
+        /// The merge stage of the entry, with 0 being the default.
+        stage: u8,
+    },
+    /// The kind of the specification, which precedes the steps of the second revision in case of ranges like `a..b`,
+    /// or is the last step for `a^@` and `a^!`.
+    Kind(crate::spec::Kind),
+}
+
+/// An owned version of [`delegate::PrefixHint`].
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub enum PrefixHint {
+    /// The prefix must be a commit.
+    MustBeCommit,
+    /// The prefix refers to a commit, anchored to a ref and a revision generation in its future.
+    DescribeAnchor {
+        /// The name of the reference, like `v1.2.3` or `main`.
+        ref_name: BString,
+        /// The future generation of the commit we look for, with 0 meaning the commit is referenced by
+        /// `ref_name` directly.
+        generation: usize,
+    },
+}
+
+/// An owned version of [`delegate::PeelTo`].
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub enum PeelTo {
+    /// An object of the given kind.
+    ObjectKind(gix_object::Kind),
+    /// Ensure the object at hand exists and is valid, without peeling it.
+    ValidObject,
+    /// Follow an annotated tag object recursively until an object is found.
+    RecursiveTagObject,
+    /// The path to drill into as seen relative to the current tree-ish.
+    Path(BString),
+}
+
+impl From<delegate::PrefixHint<'_>> for PrefixHint {
+    fn from(value: delegate::PrefixHint<'_>) -> Self {
+        match value {
+            delegate::PrefixHint::MustBeCommit => PrefixHint::MustBeCommit,
+            delegate::PrefixHint::DescribeAnchor { ref_name, generation } => PrefixHint::DescribeAnchor {
+                ref_name: ref_name.to_owned(),
+                generation,
+            },
+        }
+    }
+}
+
+impl From<delegate::PeelTo<'_>> for PeelTo {
+    fn from(value: delegate::PeelTo<'_>) -> Self {
+        match value {
+            delegate::PeelTo::ObjectKind(kind) => PeelTo::ObjectKind(kind),
+            delegate::PeelTo::ValidObject => PeelTo::ValidObject,
+            delegate::PeelTo::RecursiveTagObject => PeelTo::RecursiveTagObject,
+            delegate::PeelTo::Path(path) => PeelTo::Path(path.to_owned()),
+        }
+    }
+}
+
+pub(crate) mod function {
+    use bstr::BStr;
+
+    use super::Step;
+    use crate::spec::parse::{delegate, Delegate, Error};
+
+    /// Parse `input` as revision specification and return the steps needed to resolve it, in order.
+    ///
+    /// This is a data-driven alternative to implementing a [`Delegate`] for [`parse()`][crate::spec::parse()],
+    /// which receives the same information as callbacks. For example, `HEAD~2^{tree}` is planned as
+    /// [resolving](Step::ResolveRef) `HEAD`, [traversing](Step::Traverse) to its second ancestor and
+    /// [peeling](Step::PeelUntil) the result to a tree.
+    pub fn plan(input: &BStr) -> Result<Vec<Step>, Error> {
+        let mut rec = Recorder::default();
+        crate::spec::parse(input, &mut rec)?;
+        Ok(rec.steps)
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        steps: Vec<Step>,
+    }
+
+    impl Recorder {
+        fn push(&mut self, step: Step) -> Option<()> {
+            self.steps.push(step);
+            Some(())
+        }
+    }
+
+    impl delegate::Revision for Recorder {
+        fn find_ref(&mut self, name: &BStr) -> Option<()> {
+            self.push(Step::ResolveRef(name.to_owned()))
+        }
+
+        fn disambiguate_prefix(
+            &mut self,
+            prefix: gix_hash::Prefix,
+            hint: Option<delegate::PrefixHint<'_>>,
+        ) -> Option<()> {
+            self.push(Step::DisambiguatePrefix {
+                prefix,
+                hint: hint.map(Into::into),
+            })
+        }
+
+        fn reflog(&mut self, query: delegate::ReflogLookup) -> Option<()> {
+            self.push(Step::Reflog(query))
+        }
+
+        fn nth_checked_out_branch(&mut self, branch_no: usize) -> Option<()> {
+            self.push(Step::NthCheckedOutBranch(branch_no))
+        }
+
+        fn sibling_branch(&mut self, kind: delegate::SiblingBranch) -> Option<()> {
+            self.push(Step::SiblingBranch(kind))
+        }
+    }
+
+    impl delegate::Navigate for Recorder {
+        fn traverse(&mut self, kind: delegate::Traversal) -> Option<()> {
+            self.push(Step::Traverse(kind))
+        }
+
+        fn peel_until(&mut self, kind: delegate::PeelTo<'_>) -> Option<()> {
+            self.push(Step::PeelUntil(kind.into()))
+        }
+
+        fn find(&mut self, regex: &BStr, negated: bool) -> Option<()> {
+            self.push(Step::SearchMessage {
+                regex: regex.to_owned(),
+                negated,
+            })
+        }
+
+        fn index_lookup(&mut self, path: &BStr, stage: u8) -> Option<()> {
+            self.push(Step::IndexLookup {
+                path: path.to_owned(),
+                stage,
+            })
+        }
+    }
+
+    impl delegate::Kind for Recorder {
+        fn kind(&mut self, kind: crate::spec::Kind) -> Option<()> {
+            self.push(Step::Kind(kind))
+        }
+    }
+
+    impl Delegate for Recorder {
+        fn done(&mut self) {}
+    }
+}
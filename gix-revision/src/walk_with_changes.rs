@@ -0,0 +1,153 @@
+use gix_hash::ObjectId;
+use gix_object::bstr::BString;
+
+use crate::{Graph, PriorityQueue};
+
+/// The error returned by [`walk_with_changes()`][function::walk_with_changes()] and its [`Iter`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Lookup(#[from] gix_object::find::existing_iter::Error),
+    #[error("A commit could not be decoded during traversal")]
+    Decode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Parent(#[from] crate::graph::commit::iter_parents::Error),
+    #[error("Could not diff the tree of commit {} against the tree of its first parent", oid.to_hex())]
+    Diff {
+        #[source]
+        err: gix_diff::tree::changes::Error,
+        oid: ObjectId,
+    },
+}
+
+/// An iterator over commits along with the paths they changed compared to their first parent,
+/// created by [`walk_with_changes()`][function::walk_with_changes()].
+///
+/// Commits are returned in order of their commit-time, youngest first, as they are discovered.
+pub struct Iter<'graph, 'find, T, Find> {
+    graph: &'graph mut Graph<'find, T>,
+    objects: Find,
+    queue: PriorityQueue<gix_date::SecondsSinceUnixEpoch, ObjectId>,
+    /// All commits that were queued so far, to return each commit only once.
+    seen: gix_hashtable::HashSet<ObjectId>,
+    remaining: Option<usize>,
+    state: gix_diff::tree::State,
+    lhs_buf: Vec<u8>,
+    rhs_buf: Vec<u8>,
+}
+
+pub(crate) mod function {
+    use gix_hash::ObjectId;
+
+    use super::{Error, Iter};
+    use crate::{Graph, PriorityQueue};
+
+    /// Walk all commits reachable from `tips` in `graph`, youngest first, and return an iterator which yields each commit
+    /// along with the paths of all blobs and submodules it changed compared to its first parent as it walks.
+    /// `objects` is used to obtain the trees to diff, and typically is the same database that `graph` uses.
+    ///
+    /// Root commits, and commits whose first parent isn't available like in shallow clones, are compared to the empty tree.
+    /// If `limit` is `Some(n)`, the iteration stops after `n` commits were returned.
+    ///
+    /// `graph` is only used to look up commits, possibly with the help of its commit-graph, and remains unchanged.
+    /// Note that this isn't a method on [`Graph`] as it's defined in `gix-revwalk`, which doesn't depend on `gix-diff`.
+    pub fn walk_with_changes<'graph, 'find, T, Find: gix_object::Find>(
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        graph: &'graph mut Graph<'find, T>,
+        objects: Find,
+        limit: Option<usize>,
+    ) -> Result<Iter<'graph, 'find, T, Find>, Error> {
+        let mut queue = PriorityQueue::new();
+        let mut seen = gix_hashtable::HashSet::default();
+        for tip in tips {
+            let tip = tip.into();
+            if !seen.insert(tip) {
+                continue;
+            }
+            let commit_time = graph.lookup(&tip)?.committer_timestamp()?;
+            queue.insert(commit_time, tip);
+        }
+        Ok(Iter {
+            graph,
+            objects,
+            queue,
+            seen,
+            remaining: limit,
+            state: Default::default(),
+            lhs_buf: Vec::new(),
+            rhs_buf: Vec::new(),
+        })
+    }
+}
+
+impl<'graph, 'find, T, Find: gix_object::Find> Iter<'graph, 'find, T, Find> {
+    fn changes_of(&mut self, id: ObjectId) -> Result<Vec<BString>, Error> {
+        use gix_object::FindExt;
+
+        let (tree_id, parent_ids) = {
+            let commit = self.graph.lookup(&id)?;
+            (commit.tree_id()?, commit.iter_parents().collect::<Result<Vec<_>, _>>()?)
+        };
+        let mut first_parent_tree_id = None;
+        for (parent_index, parent_id) in parent_ids.into_iter().enumerate() {
+            let is_first_parent = parent_index == 0;
+            let is_new = self.seen.insert(parent_id);
+            if !(is_first_parent || is_new) {
+                continue;
+            }
+            let parent = match self.graph.try_lookup(&parent_id)? {
+                Some(parent) => parent,
+                None => continue,
+            };
+            if is_first_parent {
+                first_parent_tree_id = Some(parent.tree_id()?);
+            }
+            if is_new {
+                self.queue.insert(parent.committer_timestamp()?, parent_id);
+            }
+        }
+
+        let lhs = first_parent_tree_id
+            .map(|tree_id| self.objects.find_tree_iter(&tree_id, &mut self.lhs_buf))
+            .transpose()?;
+        let rhs = self.objects.find_tree_iter(&tree_id, &mut self.rhs_buf)?;
+        let mut recorder = gix_diff::tree::Recorder::default();
+        gix_diff::tree::Changes::from(lhs)
+            .needed_to_obtain(rhs, &mut self.state, &self.objects, &mut recorder)
+            .map_err(|err| Error::Diff { err, oid: id })?;
+        Ok(recorder
+            .records
+            .into_iter()
+            .filter_map(|change| {
+                use gix_diff::tree::recorder::Change::*;
+                match change {
+                    Addition { entry_mode, path, .. } | Deletion { entry_mode, path, .. } => {
+                        (!entry_mode.is_tree()).then_some(path)
+                    }
+                    Modification {
+                        previous_entry_mode,
+                        entry_mode,
+                        path,
+                        ..
+                    } => (!(previous_entry_mode.is_tree() && entry_mode.is_tree())).then_some(path),
+                }
+            })
+            .collect())
+    }
+}
+
+impl<'graph, 'find, T, Find: gix_object::Find> Iterator for Iter<'graph, 'find, T, Find> {
+    type Item = Result<(ObjectId, Vec<BString>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(remaining) = self.remaining.as_mut() {
+            if *remaining == 0 {
+                return None;
+            }
+            *remaining -= 1;
+        }
+        let id = self.queue.pop_value()?;
+        Some(self.changes_of(id).map(|paths| (id, paths)))
+    }
+}
@@ -13,11 +13,18 @@ fn exact_match_with_dirty_and_long() {
         depth: 0,
         name_by_oid: Default::default(),
         commits_seen: 0,
+        candidates_seen: 0,
+        dirty_suffix: None,
+        separator: "-".into(),
+        abbrev: None,
+        long: false,
     }
     .into_format(7);
     assert!(format.is_exact_match());
+    assert!(!format.is_long_form());
     assert_eq!(format.to_string(), "main");
     assert_eq!(format.long(true).to_string(), "main-0-gb920bbb");
+    assert!(format.is_long_form());
 
     format.dirty_suffix = Some("dirty".into());
     assert_eq!(format.long(false).to_string(), "main-dirty");
@@ -27,6 +34,7 @@ fn exact_match_with_dirty_and_long() {
     format.depth = 42;
     assert!(!format.is_exact_match());
     assert_eq!(format.long(false).to_string(), "main-42-gb920bbb");
+    assert!(format.is_long_form(), "it's the long form whenever there is a depth");
 
     format.dirty_suffix = Some("dirty".into());
     assert_eq!(format.to_string(), "main-42-gb920bbb-dirty");
@@ -41,6 +49,11 @@ fn show_abbrev_hash_if_no_name_is_known() {
         depth: 0,
         name_by_oid: Default::default(),
         commits_seen: 0,
+        candidates_seen: 0,
+        dirty_suffix: None,
+        separator: "-".into(),
+        abbrev: None,
+        long: false,
     }
     .into_format(7);
     assert!(
@@ -49,8 +62,88 @@ fn show_abbrev_hash_if_no_name_is_known() {
     );
     assert_eq!(format.long(false).to_string(), "b920bbb");
     assert_eq!(format.long(true).to_string(), "b920bbb");
+    assert!(!format.is_long_form(), "without name, there is no long form");
 
     format.dirty_suffix = Some("dirty".into());
     assert_eq!(format.long(false).to_string(), "b920bbb-dirty");
     assert_eq!(format.long(true).to_string(), "b920bbb-dirty");
 }
+
+#[test]
+fn custom_separator() {
+    let mut format = describe::Outcome {
+        name: Some(Cow::Borrowed(b"v1.0".as_bstr())),
+        id: hex_to_id("b920bbb055e1efb9080592a409d3975738b6efb3"),
+        depth: 3,
+        name_by_oid: Default::default(),
+        commits_seen: 0,
+        candidates_seen: 0,
+        dirty_suffix: None,
+        separator: ".".into(),
+        abbrev: None,
+        long: false,
+    }
+    .into_format(7);
+    assert_eq!(format.to_string(), "v1.0.3.gb920bbb");
+
+    format.dirty_suffix = Some("dirty".into());
+    assert_eq!(
+        format.to_string(),
+        "v1.0.3.gb920bbb-dirty",
+        "the dirty suffix isn't affected by the separator"
+    );
+
+    format.depth = 0;
+    assert_eq!(format.to_string(), "v1.0-dirty");
+    assert_eq!(format.long(true).to_string(), "v1.0.0.gb920bbb-dirty");
+}
+
+#[test]
+fn abbrev_and_long_are_taken_from_the_outcome() {
+    let outcome = describe::Outcome {
+        name: Some(Cow::Borrowed(b"v1.0".as_bstr())),
+        id: hex_to_id("b920bbb055e1efb9080592a409d3975738b6efb3"),
+        depth: 0,
+        name_by_oid: Default::default(),
+        commits_seen: 0,
+        candidates_seen: 0,
+        dirty_suffix: None,
+        separator: "-".into(),
+        abbrev: Some(12),
+        long: true,
+    };
+    assert_eq!(outcome.clone().into_format(7).to_string(), "v1.0-0-gb920bbb055e1");
+    assert_eq!(
+        outcome.clone().into_format(14).to_string(),
+        "v1.0-0-gb920bbb055e1ef",
+        "the abbreviation is a minimum and grows to stay unambiguous"
+    );
+
+    let mut format = describe::Outcome {
+        abbrev: Some(0),
+        depth: 3,
+        ..outcome.clone()
+    }
+    .into_format(7);
+    assert_eq!(
+        format.to_string(),
+        "v1.0",
+        "the hash suffix is omitted entirely, even in long form"
+    );
+    assert!(!format.is_long_form());
+
+    format.dirty_suffix = Some("dirty".into());
+    assert_eq!(format.to_string(), "v1.0-dirty");
+
+    let format = describe::Outcome {
+        name: None,
+        abbrev: Some(0),
+        ..outcome
+    }
+    .into_format(7);
+    assert_eq!(
+        format.to_string(),
+        "b920bbb055e1efb9080592a409d3975738b6efb3",
+        "without name, the full hash is used"
+    );
+}
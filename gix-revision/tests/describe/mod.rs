@@ -112,6 +112,32 @@ fn not_enough_candidates() -> crate::Result {
                 res.depth, 3,
                 "it calculates the final number of commits even though it aborted early"
             );
+            assert_eq!(
+                res.candidates_seen, 1,
+                "the cap was hit, so there may have been better candidates"
+            );
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: vec![
+                (hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"), name.clone()),
+                (
+                    hex_to_id("9152eeee2328073cf23dcf8e90c949170b711659"),
+                    b"at-b1c1".as_bstr().into(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.name, Some(name.clone()));
+            assert_eq!(res.candidates_seen, 2, "both names were considered");
             Ok(())
         },
     )
@@ -197,6 +223,196 @@ fn typical_usecases() -> crate::Result {
     )
 }
 
+#[test]
+fn first_parent_ignores_closer_names_on_merged_branches() -> crate::Result {
+    for (first_parent, expected_name, expected_depth) in [(false, "at-b1c1", 7), (true, "at-c1", 5)] {
+        run_test(
+            std::convert::identity,
+            |_| describe::Options {
+                name_by_oid: vec![
+                    (
+                        hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03"),
+                        Cow::Borrowed(b"at-c1".as_bstr()),
+                    ),
+                    (
+                        hex_to_id("9152eeee2328073cf23dcf8e90c949170b711659"),
+                        b"at-b1c1".as_bstr().into(),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                first_parent,
+                ..Default::default()
+            },
+            |res, _id| {
+                let res = res?.expect("candidate found");
+                assert_eq!(
+                    res.name.as_deref(),
+                    Some(expected_name.into()),
+                    "first-parent: {first_parent}: only names on the first-parent chain are used if set"
+                );
+                assert_eq!(
+                    res.depth, expected_depth,
+                    "the depth is counted along the first-parent chain if set: merge, c5, c4, c3 and c2, just like `git describe --first-parent`"
+                );
+                Ok(())
+            },
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn depth_can_be_counted_along_first_parent_only() -> crate::Result {
+    let name = Cow::Borrowed(b"at-c1".as_bstr());
+    for (depth_first_parent_only, expected_depth) in [(false, 7), (true, 5)] {
+        run_test(
+            std::convert::identity,
+            |_| describe::Options {
+                name_by_oid: vec![(hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03"), name.clone())]
+                    .into_iter()
+                    .collect(),
+                depth_first_parent_only,
+                ..Default::default()
+            },
+            |res, _id| {
+                let res = res?.expect("candidate found");
+                assert_eq!(res.name, Some(name.clone()));
+                assert_eq!(
+                    res.depth, expected_depth,
+                    "the side branch isn't counted along the first parent, but all commits not reachable by the name are counted otherwise"
+                );
+                Ok(())
+            },
+        )?;
+    }
+
+    let name = Cow::Borrowed(b"at-b1c1".as_bstr());
+    let depths = std::cell::RefCell::new(Vec::new());
+    for depth_first_parent_only in [false, true] {
+        run_test(
+            std::convert::identity,
+            |_| describe::Options {
+                name_by_oid: vec![(hex_to_id("9152eeee2328073cf23dcf8e90c949170b711659"), name.clone())]
+                    .into_iter()
+                    .collect(),
+                depth_first_parent_only,
+                ..Default::default()
+            },
+            |res, _id| {
+                depths.borrow_mut().push(res?.expect("candidate found").depth);
+                Ok(())
+            },
+        )?;
+    }
+    assert!(
+        depths.borrow().windows(2).all(|w| w[0] == w[1]),
+        "names on side branches aren't on the first-parent chain, so they keep their regular depth"
+    );
+    Ok(())
+}
+
+#[test]
+fn exact_matches_have_a_depth_of_zero_which_is_only_displayed_in_long_form() -> crate::Result {
+    let name = Cow::Borrowed(b"main".as_bstr());
+    run_test(
+        std::convert::identity,
+        |id| describe::Options {
+            name_by_oid: vec![(id, name.clone())].into_iter().collect(),
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert!(res.is_exact_match());
+            assert_eq!(
+                res.depth, 0,
+                "the depth is available without parsing the formatted string"
+            );
+            let mut format = res.into_format(7);
+            assert_eq!(format.to_string(), "main");
+            assert_eq!(format.long(true).to_string(), "main-0-g01ec18a");
+            Ok(())
+        },
+    )?;
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            fallback_to_oid: true,
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("fallback");
+            assert_eq!(res.depth, 0);
+            assert!(!res.is_exact_match(), "without a name, there is nothing to match");
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn dirty_suffix_is_appended_to_exact_matches() -> crate::Result {
+    let name = Cow::Borrowed(b"main".as_bstr());
+    run_test(
+        std::convert::identity,
+        |id| describe::Options {
+            name_by_oid: vec![(id, name.clone())].into_iter().collect(),
+            dirty: true,
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.depth, 0, "it's still an exact match");
+            assert_eq!(res.dirty_suffix.as_deref(), Some("dirty"));
+            assert_eq!(res.into_format(7).to_string(), "main-dirty");
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn dirty_suffix_is_appended_when_ahead_of_name() -> crate::Result {
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: vec![(
+                hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+                Cow::Borrowed(b"at-c5".as_bstr()),
+            )]
+            .into_iter()
+            .collect(),
+            first_parent: true,
+            dirty: true,
+            dirty_suffix: "modified".into(),
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.depth, 1);
+            assert_eq!(res.into_format(7).to_string(), "at-c5-1-g01ec18a-modified");
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn dirty_suffix_is_ignored_if_not_dirty() -> crate::Result {
+    let name = Cow::Borrowed(b"main".as_bstr());
+    run_test(
+        std::convert::identity,
+        |id| describe::Options {
+            name_by_oid: vec![(id, name.clone())].into_iter().collect(),
+            dirty_suffix: "modified".into(),
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.dirty_suffix, None);
+            assert_eq!(res.into_format(7).to_string(), "main");
+            Ok(())
+        },
+    )
+}
+
 #[test]
 fn shallow_yields_no_result_if_provided_refs_are_in_truncated_part_of_history() -> crate::Result {
     run_test(
@@ -246,6 +462,560 @@ fn shallow_yields_result_if_refs_are_available() -> crate::Result {
     )
 }
 
+#[test]
+fn name_and_depth_matches_describe() -> crate::Result {
+    let store = odb_at(".");
+    let commit_id = hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b");
+    let name_by_oid: gix_hashtable::HashMap<_, _> = vec![
+        (
+            hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+            Cow::Borrowed(b"at-c5".as_bstr()),
+        ),
+        (
+            hex_to_id("9152eeee2328073cf23dcf8e90c949170b711659"),
+            b"at-b1c1".as_bstr().into(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let mut graph = gix_revision::Graph::new(&store, None);
+    for first_parent in [false, true] {
+        for max_candidates in [0, 1, 10] {
+            let expected = gix_revision::describe(
+                &commit_id,
+                &mut graph,
+                describe::Options {
+                    name_by_oid: name_by_oid.clone(),
+                    max_candidates,
+                    first_parent,
+                    ..Default::default()
+                },
+            )?
+            .map(|outcome| (outcome.name.expect("no fallback").into_owned(), outcome.depth));
+            let actual = describe::name_and_depth(&commit_id, &mut graph, &name_by_oid, max_candidates, first_parent)?
+                .map(|(name, depth)| (name.to_owned(), depth));
+            assert_eq!(
+                actual, expected,
+                "first_parent = {first_parent}, max_candidates = {max_candidates}"
+            );
+        }
+    }
+
+    let exact: gix_hashtable::HashMap<_, _> = Some((commit_id, Cow::Borrowed(b"main".as_bstr())))
+        .into_iter()
+        .collect();
+    assert_eq!(
+        describe::name_and_depth(&commit_id, &mut graph, &exact, 0, false)?,
+        Some((b"main".as_bstr(), 0)),
+        "exact matches are found even without candidates"
+    );
+    Ok(())
+}
+
+#[test]
+fn depth_is_the_amount_of_commits_not_reachable_from_the_name() -> crate::Result {
+    let store = odb_at(".");
+    let commit_id = hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b");
+    let mut graph = gix_revision::Graph::new(&store, None);
+    for (tag_id, name) in [
+        ("134385f6d781b7e97062102c6a483440bfda2a03", "at-c1"),
+        ("efd9a841189668f1bab5b8ebade9cd0a1b139a37", "at-c5"),
+    ] {
+        let tag_id = hex_to_id(tag_id);
+        let ahead_of_tag = {
+            let from_tag = graph.reachable_from(Some(tag_id), &[])?;
+            graph
+                .reachable_from(Some(commit_id), &[])?
+                .difference(&from_tag)
+                .count() as u32
+        };
+        let name_by_oid: gix_hashtable::HashMap<_, _> = Some((tag_id, Cow::Borrowed(name.as_bytes().as_bstr())))
+            .into_iter()
+            .collect();
+
+        let (_name, depth) =
+            describe::name_and_depth(&commit_id, &mut graph, &name_by_oid, 10, false)?.expect("the tag is reachable");
+        assert_eq!(
+            depth, ahead_of_tag,
+            "{name}: the depth is available without creating an outcome or formatting it"
+        );
+        let outcome = gix_revision::describe(
+            &commit_id,
+            &mut graph,
+            describe::Options {
+                name_by_oid,
+                ..Default::default()
+            },
+        )?
+        .expect("the tag is reachable");
+        assert_eq!(outcome.depth, ahead_of_tag, "{name}");
+    }
+    Ok(())
+}
+
+#[test]
+fn name_order_breaks_ties_between_candidates_at_the_same_depth() -> crate::Result {
+    let repo = gix_testtools::scripted_fixture_read_only("make_repo_with_tied_tags.sh").unwrap();
+    let store = gix_odb::at(repo.join(".git/objects"))?;
+    let merge = hex_to_id("25a657d19ea748acbcc3ef2940798759f3099e87");
+    let v1_0_1 = hex_to_id("4492d26a6261a96ead499afe929cce3ab2ec2fd7");
+    let v1_0_10 = hex_to_id("2717897b352799306cabb86dc424e49ad668a7f7");
+    let name_by_oid: gix_hashtable::HashMap<_, _> = vec![
+        (v1_0_1, Cow::Borrowed(b"v1.0.1".as_bstr())),
+        (v1_0_10, b"v1.0.10".as_bstr().into()),
+    ]
+    .into_iter()
+    .collect();
+
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for (name_order, expected) in [
+            (vec![v1_0_10, v1_0_1], "v1.0.10"),
+            (vec![v1_0_1, v1_0_10], "v1.0.1"),
+            (vec![v1_0_10], "v1.0.10"),
+            (vec![v1_0_1], "v1.0.1"),
+        ] {
+            let res = gix_revision::describe(
+                &merge,
+                &mut graph,
+                describe::Options {
+                    name_by_oid: name_by_oid.clone(),
+                    name_order,
+                    ..Default::default()
+                },
+            )?
+            .expect("candidate found");
+            assert_eq!(
+                res.name,
+                Some(Cow::Borrowed(expected.as_bytes().as_bstr())),
+                "the first listed name wins, and listed names win over unlisted ones"
+            );
+            assert_eq!(res.depth, 3, "both names are at the same depth, like in `git describe`");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn names_beyond_the_max_depth_are_not_used() -> crate::Result {
+    let far = (
+        hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03"),
+        Cow::Borrowed(b"at-c1".as_bstr()),
+    );
+    let near = (
+        hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+        Cow::Borrowed(b"at-c5".as_bstr()),
+    );
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: vec![far.clone(), near.clone()].into_iter().collect(),
+            max_depth_for_candidates: Some(5),
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.name, Some(near.1.clone()), "the near name is within the limit");
+            assert_eq!(res.depth, 3);
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: Some(far.clone()).into_iter().collect(),
+            ..Default::default()
+        },
+        |res, _id| {
+            assert_eq!(
+                res?.expect("candidate found").depth,
+                7,
+                "without limit, the far name is used"
+            );
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: Some(far.clone()).into_iter().collect(),
+            max_depth_for_candidates: Some(5),
+            ..Default::default()
+        },
+        |res, _id| {
+            assert!(res?.is_none(), "the far name is too far away");
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: Some(far.clone()).into_iter().collect(),
+            max_depth_for_candidates: Some(5),
+            fallback_to_oid: true,
+            ..Default::default()
+        },
+        |res, id| {
+            let res = res?.expect("fallback");
+            assert_eq!(res.name, None, "the hash is used instead");
+            assert_eq!(res.into_format(7).to_string(), id.to_hex_with_len(7).to_string());
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn names_can_be_filtered_with_match_and_exclude_patterns() -> crate::Result {
+    let far = (
+        hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03"),
+        Cow::Borrowed(b"at-c1".as_bstr()),
+    );
+    let near = (
+        hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+        Cow::Borrowed(b"at-c5".as_bstr()),
+    );
+    let names = || vec![far.clone(), near.clone()].into_iter().collect();
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: names(),
+            match_patterns: vec!["*c1".into(), "no-match".into()],
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.name, Some(far.1.clone()), "only the far name matches");
+            assert_eq!(res.depth, 7);
+            assert_eq!(res.name_by_oid.len(), 1, "filtered names aren't returned");
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: names(),
+            match_patterns: vec!["at-*".into()],
+            exclude_patterns: vec!["*5".into()],
+            ..Default::default()
+        },
+        |res, _id| {
+            assert_eq!(
+                res?.expect("candidate found").name,
+                Some(far.1.clone()),
+                "exclusions win over matches"
+            );
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: names(),
+            exclude_patterns: vec!["at-c?".into()],
+            ..Default::default()
+        },
+        |res, _id| {
+            assert!(res?.is_none(), "all names were excluded");
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: names(),
+            match_patterns: vec!["v*".into()],
+            fallback_to_oid: true,
+            ..Default::default()
+        },
+        |res, id| {
+            let res = res?.expect("fallback");
+            assert_eq!(res.name, None, "no name matches, so the hash is used instead");
+            assert_eq!(res.into_format(7).to_string(), id.to_hex_with_len(7).to_string());
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn abbrev_and_long_are_passed_on_to_the_format() -> crate::Result {
+    let near = (
+        hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+        Cow::Borrowed(b"at-c5".as_bstr()),
+    );
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: Some(near.clone()).into_iter().collect(),
+            abbrev: Some(10),
+            ..Default::default()
+        },
+        |res, id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(
+                res.into_format(7).to_string(),
+                format!("at-c5-3-g{}", id.to_hex_with_len(10))
+            );
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |id| describe::Options {
+            name_by_oid: Some((id, Cow::Borrowed(b"exact".as_bstr()))).into_iter().collect(),
+            long: true,
+            ..Default::default()
+        },
+        |res, id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(
+                res.into_format(7).to_string(),
+                format!("exact-0-g{}", id.to_hex_with_len(7))
+            );
+            Ok(())
+        },
+    )?;
+
+    run_test(
+        std::convert::identity,
+        |_| describe::Options {
+            name_by_oid: Some(near.clone()).into_iter().collect(),
+            abbrev: Some(0),
+            long: true,
+            ..Default::default()
+        },
+        |res, _id| {
+            let res = res?.expect("candidate found");
+            assert_eq!(res.into_format(7).to_string(), "at-c5", "only the name is shown");
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn abbreviation_can_be_unique_among_commits_in_commit_graph() -> crate::Result {
+    let store = odb_at(".");
+    let commit_graph = gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info"))?;
+    let mut graph = gix_revision::Graph::new(&store, None);
+    let c4 = hex_to_id("9556057aee5abb06912922e9f26c46386a816822");
+    let res = gix_revision::describe(
+        &c4,
+        &mut graph,
+        describe::Options {
+            name_by_oid: vec![(
+                hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03"),
+                Cow::Borrowed(b"at-c1".as_bstr()),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        },
+    )?
+    .expect("candidate found");
+    assert_eq!(
+        res.clone().into_format_unique_in(1, &commit_graph).to_string(),
+        "at-c1-3-g95",
+        "9152eee and 9902e3c share the first character, so one more is needed"
+    );
+    assert_eq!(
+        res.clone().into_format_unique_in(4, &commit_graph).to_string(),
+        "at-c1-3-g9556",
+        "the minimum length is respected"
+    );
+
+    let mut res = res;
+    res.id = hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b");
+    assert_eq!(
+        res.clone().into_format_unique_in(1, &commit_graph).to_string(),
+        "at-c1-3-g0",
+        "no other commit starts with 0"
+    );
+    res.id = gix_hash::Kind::Sha1.null();
+    assert_eq!(
+        res.into_format_unique_in(2, &commit_graph).to_string(),
+        "at-c1-3-g00",
+        "commits that aren't in the graph use the minimum length"
+    );
+    Ok(())
+}
+
+#[test]
+fn nearest_names_of_many_commits_match_the_ones_of_individual_commits() -> crate::Result {
+    let store = odb_at(".");
+    let head = hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b");
+    let name_by_oid: gix_hashtable::HashMap<_, _> = [
+        ("134385f6d781b7e97062102c6a483440bfda2a03", "at-c1"),
+        ("9152eeee2328073cf23dcf8e90c949170b711659", "at-b1c1"),
+        ("efd9a841189668f1bab5b8ebade9cd0a1b139a37", "at-c5"),
+    ]
+    .into_iter()
+    .map(|(id, name)| (hex_to_id(id), Cow::Borrowed(name.as_bytes().as_bstr())))
+    .collect();
+
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        let mut commits: Vec<_> = graph.reachable_from(Some(head), &[])?.into_iter().collect();
+        commits.sort();
+        assert_eq!(commits.len(), 8);
+
+        for (max_candidates, first_parent) in [(10, false), (1, false), (10, true)] {
+            let names = describe::nearest_names(&commits, &mut graph, &name_by_oid, max_candidates, first_parent)?;
+            for id in &commits {
+                let expected = describe::name_and_depth(id, &mut graph, &name_by_oid, max_candidates, first_parent)?;
+                assert_eq!(
+                    names.get(id).copied(),
+                    expected,
+                    "{id}: walking all commits at once yields the same as walking each of them"
+                );
+            }
+            assert_eq!(names.len(), 8, "all commits have the tagged root commit in their past");
+        }
+
+        let names = describe::nearest_names(&[head], &mut graph, &name_by_oid, 10, false)?;
+        assert_eq!(names[&head], ("at-c5".into(), 3), "just like `git describe --tags`");
+
+        let missing = gix_hash::Kind::Sha1.null();
+        let names = describe::nearest_names(&[missing], &mut graph, &name_by_oid, 10, false)?;
+        assert!(names.is_empty(), "missing commits are ignored");
+    }
+    Ok(())
+}
+
+#[test]
+fn commits_since_name_counts_commits_added_after_the_closest_name() -> crate::Result {
+    let store = odb_at(".");
+    let head = hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b");
+    let name_by_oid: gix_hashtable::HashMap<_, _> = [
+        ("134385f6d781b7e97062102c6a483440bfda2a03", "at-c1"),
+        ("efd9a841189668f1bab5b8ebade9cd0a1b139a37", "at-c5"),
+    ]
+    .into_iter()
+    .map(|(id, name)| (hex_to_id(id), Cow::Borrowed(name.as_bytes().as_bstr())))
+    .collect();
+    let mut graph = gix_revision::Graph::new(&store, None);
+
+    assert_eq!(
+        describe::commits_since_name(&head, &mut graph, &name_by_oid, 10)?,
+        describe::CommitsSinceName {
+            name: Some("at-c5".into()),
+            count: 3
+        },
+        "the merge and the two commits on the merged branch were added since the last release"
+    );
+    assert_eq!(
+        describe::commits_since_name(
+            &hex_to_id("efd9a841189668f1bab5b8ebade9cd0a1b139a37"),
+            &mut graph,
+            &name_by_oid,
+            10
+        )?,
+        describe::CommitsSinceName {
+            name: Some("at-c5".into()),
+            count: 0
+        },
+        "nothing was added to a release"
+    );
+    assert_eq!(
+        describe::commits_since_name(&head, &mut graph, &Default::default(), 10)?,
+        describe::CommitsSinceName { name: None, count: 8 },
+        "without names, all commits are counted"
+    );
+    Ok(())
+}
+
+#[test]
+fn first_parent_count_ignores_merged_branches() -> crate::Result {
+    let store = odb_at(".");
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for (commit_id, expected) in [
+            ("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b", 6),
+            ("efd9a841189668f1bab5b8ebade9cd0a1b139a37", 5),
+            ("9152eeee2328073cf23dcf8e90c949170b711659", 5),
+            ("134385f6d781b7e97062102c6a483440bfda2a03", 1),
+        ] {
+            assert_eq!(
+                describe::first_parent_count(&mut graph, &hex_to_id(commit_id))?,
+                expected,
+                "{commit_id}: merged commits of the side branch don't count, just like `git rev-list --first-parent --count`"
+            );
+        }
+    }
+
+    let store = odb_at("shallow-1-clone");
+    let mut graph = gix_revision::Graph::new(&store, None);
+    assert_eq!(
+        describe::first_parent_count(&mut graph, &hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"))?,
+        1,
+        "missing parents end the chain"
+    );
+    Ok(())
+}
+
+#[test]
+fn contains_names_the_oldest_name_that_contains_a_commit() -> crate::Result {
+    let store = odb_at(".");
+    let mut name_by_oid: gix_hashtable::HashMap<_, _> = [
+        ("134385f6d781b7e97062102c6a483440bfda2a03", "at-c1"),
+        ("9152eeee2328073cf23dcf8e90c949170b711659", "at-b1c1"),
+        ("efd9a841189668f1bab5b8ebade9cd0a1b139a37", "at-c5"),
+    ]
+    .into_iter()
+    .map(|(id, name)| (hex_to_id(id), Cow::Borrowed(name.as_bytes().as_bstr())))
+    .collect();
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for (commit_id, expected) in [
+            ("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b", None),
+            ("ce2e8ffaa9608a26f7b21afc1db89cadb54fd353", None),
+            ("efd9a841189668f1bab5b8ebade9cd0a1b139a37", Some("at-c5")),
+            ("9556057aee5abb06912922e9f26c46386a816822", Some("at-b1c1~1")),
+            ("9902e3c3e8f0c569b4ab295ddf473e6de763e1e7", Some("at-b1c1~3")),
+            ("134385f6d781b7e97062102c6a483440bfda2a03", Some("at-c1")),
+        ] {
+            assert_eq!(
+                describe::contains(&hex_to_id(commit_id), &mut graph, &name_by_oid)?,
+                expected.map(Into::into),
+                "{commit_id}: the same as `git describe --contains`"
+            );
+        }
+
+        name_by_oid.insert(
+            hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"),
+            Cow::Borrowed(b"head".as_bstr()),
+        );
+        assert_eq!(
+            describe::contains(
+                &hex_to_id("ce2e8ffaa9608a26f7b21afc1db89cadb54fd353"),
+                &mut graph,
+                &name_by_oid
+            )?,
+            Some("head^2".into()),
+            "merged parents are named by their parent number"
+        );
+        name_by_oid.remove(&hex_to_id("01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"));
+    }
+    Ok(())
+}
+
 fn odb_at(name: &str) -> gix_odb::Handle {
     gix_odb::at(fixture_path().join(name).join(".git/objects")).unwrap()
 }
@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+echo a >a
+git add a && git commit -q -m c1
+mkdir dir && echo b >dir/b
+git add dir && git commit -q -m c2
+echo a2 >a && git rm -q dir/b && echo c >c
+git add . && git commit -q -m c3
+
+git checkout -q -b side main~1
+echo s >side
+git add side && git commit -q -m s1
+
+git checkout -q main
+git merge -q side -m merge
@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+git commit -q --allow-empty -m c1
+
+git checkout -q -b branch1
+git commit -q --allow-empty -m b1c1
+git tag v1.0.1
+
+git checkout -q -b branch2 main
+git commit -q --allow-empty -m b2c1
+git tag v1.0.10
+
+git checkout -q main
+git merge -q branch1 branch2 -m merge
+
+git commit-graph write --no-progress --reachable
+git repack -adq
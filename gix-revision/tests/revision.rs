@@ -1,6 +1,8 @@
 #[cfg(feature = "describe")]
 mod describe;
 mod spec;
+#[cfg(feature = "walk-with-changes")]
+mod walk_with_changes;
 pub type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + 'static>>;
 
 fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
@@ -0,0 +1,73 @@
+use gix_revision::spec::{
+    classify,
+    classify::{Kind, Options},
+};
+
+#[test]
+fn plain_branch_names_are_revisions() {
+    assert_eq!(classify("main".into(), Options::default()), Kind::Revision);
+    assert_eq!(classify("HEAD".into(), Options::default()), Kind::Revision);
+    assert_eq!(
+        classify("refs/heads/feature/login".into(), Options::default()),
+        Kind::Revision
+    );
+    assert_eq!(
+        classify("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(), Options::default()),
+        Kind::Revision
+    );
+}
+
+#[test]
+fn revision_syntax_is_always_a_revision() {
+    for input in [
+        "main~1",
+        "v1.0^{tree}",
+        "HEAD@{1}",
+        "@{upstream}",
+        ":/message",
+        "main:src/lib.rs",
+        "^main",
+    ] {
+        assert_eq!(classify(input.into(), Options::default()), Kind::Revision, "{input}");
+    }
+}
+
+#[test]
+fn ranges() {
+    for input in ["a..b", "a...b", "..b", "main^@", "main^!"] {
+        assert_eq!(classify(input.into(), Options::default()), Kind::Range, "{input}");
+    }
+}
+
+#[test]
+fn paths() {
+    for input in ["src/lib.rs", "./file", "../file", "/abs/path", "", "a path with spaces"] {
+        assert_eq!(classify(input.into(), Options::default()), Kind::Path, "{input:?}");
+    }
+}
+
+#[test]
+fn names_that_could_be_references_or_paths_are_ambiguous() {
+    for input in ["feature/login", "v1.0", "lib.rs"] {
+        assert_eq!(classify(input.into(), Options::default()), Kind::Ambiguous, "{input}");
+    }
+}
+
+#[test]
+fn ambiguous_names_can_be_forced_into_a_kind() {
+    let as_revision = Options {
+        ambiguous_as: Some(Kind::Revision),
+    };
+    let as_path = Options {
+        ambiguous_as: Some(Kind::Path),
+    };
+    assert_eq!(classify("feature/login".into(), as_revision), Kind::Revision);
+    assert_eq!(classify("feature/login".into(), as_path), Kind::Path);
+
+    assert_eq!(
+        classify("src/lib.rs".into(), as_revision),
+        Kind::Path,
+        "only ambiguous names are affected"
+    );
+    assert_eq!(classify("main~1".into(), as_path), Kind::Revision);
+}
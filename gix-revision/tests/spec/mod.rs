@@ -1,2 +1,4 @@
+mod classify;
 mod display;
 mod parse;
+mod plan;
@@ -1,4 +1,4 @@
-use gix_revision::spec;
+use gix_revision::{spec, spec::parse::delegate::Traversal};
 
 use crate::spec::parse::{parse, try_parse};
 
@@ -47,6 +47,21 @@ fn reflog_by_date_for_current_branch() {
     assert_eq!(rec.calls, 1);
 }
 
+#[test]
+fn reflog_by_relative_date_for_given_ref_name() {
+    for spec in ["main@{yesterday}", "main@{2.days.ago}", "main@{3 weeks ago}"] {
+        let rec = parse(spec);
+
+        assert!(rec.kind.is_none());
+        assert_eq!(rec.get_ref(0), "main");
+        assert!(
+            rec.current_branch_reflog_entry[0].is_some(),
+            "{spec}: relative dates are resolved against the current time"
+        );
+        assert_eq!(rec.calls, 2, "first the ref, then the reflog entry");
+    }
+}
+
 #[test]
 fn reflog_by_date_with_date_parse_failure() {
     let err = try_parse("@{foo}").unwrap_err();
@@ -160,6 +175,37 @@ fn sibling_branch_for_hash_is_invalid() {
     }
 }
 
+#[test]
+fn sibling_branch_must_directly_follow_the_branch_name() {
+    for (spec, unconsumed) in [
+        ("main~1@{u}", "@{u}"),
+        ("main^{commit}@{push}", "@{push}"),
+        ("@{u}@{push}", "@{push}"),
+        ("main@{upstream}@{u}", "@{u}"),
+    ] {
+        let err = try_parse(spec).unwrap_err();
+        assert!(
+            matches!(err, spec::parse::Error::UnconsumedInput {input} if input == unconsumed),
+            "{spec}: only a name may be followed by @{{upstream}} or @{{push}}"
+        );
+    }
+}
+
+#[test]
+fn sibling_branch_can_be_navigated_and_used_in_ranges() {
+    let rec = parse("main@{u}~1");
+    assert_eq!(rec.get_ref(0), "main");
+    assert_eq!(rec.sibling_branch[0].as_deref(), Some("Upstream"));
+    assert_eq!(rec.traversal, [Traversal::NthAncestor(1)]);
+    assert_eq!(rec.calls, 3);
+
+    let rec = parse("@{u}..@{push}");
+    assert_eq!(rec.kind, Some(spec::Kind::RangeBetween));
+    assert_eq!(rec.sibling_branch[0].as_deref(), Some("Upstream"));
+    assert_eq!(rec.sibling_branch[1].as_deref(), Some("Push"));
+    assert_eq!(rec.calls, 3);
+}
+
 #[test]
 fn nth_checked_out_branch_for_refname_is_invalid() {
     let err = try_parse("r1@{-1}").unwrap_err();
@@ -185,6 +231,21 @@ fn nth_checked_out_branch() {
     }
 }
 
+#[test]
+fn numbers_within_braces_must_fit_into_an_integer() {
+    for (spec, number) in [
+        ("@{-99999999999999999999999}", "-99999999999999999999999"),
+        ("@{99999999999999999999999}", "99999999999999999999999"),
+        ("main@{+99999999999999999999999}", "+99999999999999999999999"),
+    ] {
+        let err = try_parse(spec).unwrap_err();
+        assert!(
+            matches!(err, spec::parse::Error::InvalidNumber {input} if input == number),
+            "{spec}: numbers that overflow aren't mistaken for dates"
+        );
+    }
+}
+
 #[test]
 fn numbers_within_braces_cannot_be_negative_zero() {
     let err = try_parse("@{-0}").unwrap_err();
@@ -89,6 +89,8 @@ fn various_valid_index_lookups_by_path_and_stage() {
         (":0:path", "path", 0),
         (":1:dir/path", "dir/path", 1),
         (":2:dir/path@{part-of-path}", "dir/path@{part-of-path}", 2),
+        (":3:dir/with:colon", "dir/with:colon", 3),
+        (":0::leading-colon", ":leading-colon", 0),
     ] {
         let rec = parse(spec);
 
@@ -235,6 +235,27 @@ fn all_characters_are_taken_verbatim_which_includes_whitespace() {
     assert_eq!(rec.get_ref(0), spec);
 }
 
+#[test]
+fn parse_many_reports_all_errors_with_their_input_index() {
+    let specs = ["HEAD", "@{", "main~1", ":/", "a..b..c", "v1.0^{tree}", "HEAD~-1"];
+    let errors = spec::parse_many(specs.into_iter().map(Into::into));
+    assert_eq!(
+        errors.iter().map(|(idx, _err)| *idx).collect::<Vec<_>>(),
+        [1, 3, 4, 6],
+        "valid specs don't produce errors, and invalid ones don't stop the parsing of the ones that follow"
+    );
+    assert!(matches!(errors[0].1, spec::parse::Error::UnclosedBracePair { .. }));
+    assert!(matches!(errors[1].1, spec::parse::Error::EmptyTopLevelRegex));
+    for (idx, err) in errors {
+        assert_eq!(
+            err.to_string(),
+            try_parse(specs[idx]).unwrap_err().to_string(),
+            "the errors are the same as when parsing individually"
+        );
+    }
+    assert!(spec::parse_many(["@", "HEAD~1..main"].into_iter().map(Into::into)).is_empty());
+}
+
 mod fuzz {
     use crate::spec::parse::{try_parse_opts, Options};
 
@@ -55,3 +55,21 @@ fn paths_have_to_be_last_but_stack_with_other_navigation() {
     );
     assert_eq!(rec.calls, 6);
 }
+
+#[test]
+fn paths_may_contain_colons() {
+    let rec = parse("HEAD:dir/a:b:c");
+
+    assert!(rec.kind.is_none());
+    assert_eq!(rec.get_ref(0), "HEAD");
+    assert_eq!(rec.peel_to, vec![PeelTo::Path("dir/a:b:c".into())]);
+    assert_eq!(rec.calls, 2);
+
+    let rec = parse("HEAD:/not-a-regex");
+    assert_eq!(
+        rec.peel_to,
+        vec![PeelTo::Path("/not-a-regex".into())],
+        "only a leading colon can start a regex"
+    );
+    assert_eq!(rec.patterns, vec![]);
+}
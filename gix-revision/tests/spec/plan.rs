@@ -0,0 +1,55 @@
+use gix_revision::{
+    spec,
+    spec::{
+        parse::delegate::{ReflogLookup, Traversal},
+        plan::{PeelTo, Step},
+    },
+};
+
+#[test]
+fn navigation_and_peeling() -> crate::Result {
+    assert_eq!(
+        spec::plan("HEAD~2^{tree}".into())?,
+        [
+            Step::ResolveRef("HEAD".into()),
+            Step::Traverse(Traversal::NthAncestor(2)),
+            Step::PeelUntil(PeelTo::ObjectKind(gix_object::Kind::Tree)),
+        ]
+    );
+    assert_eq!(
+        spec::plan("main@{1}^2:/fix".into())?,
+        [
+            Step::ResolveRef("main".into()),
+            Step::Reflog(ReflogLookup::Entry(1)),
+            Step::Traverse(Traversal::NthParent(2)),
+            Step::PeelUntil(PeelTo::Path("/fix".into())),
+        ]
+    );
+    assert_eq!(
+        spec::plan(":/!-fix".into())?,
+        [Step::SearchMessage {
+            regex: "fix".into(),
+            negated: true
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn ranges() -> crate::Result {
+    assert_eq!(
+        spec::plan("a..b~1".into())?,
+        [
+            Step::ResolveRef("a".into()),
+            Step::Kind(spec::Kind::RangeBetween),
+            Step::ResolveRef("b".into()),
+            Step::Traverse(Traversal::NthAncestor(1)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_specs_fail_like_parsing() {
+    assert!(spec::plan("main@{".into()).is_err());
+}
@@ -0,0 +1,60 @@
+use gix_object::bstr::BString;
+
+use crate::hex_to_id;
+
+fn odb() -> gix_odb::Handle {
+    let dir = gix_testtools::scripted_fixture_read_only("make_repo_with_changes.sh").unwrap();
+    gix_odb::at(dir.join(".git/objects")).unwrap()
+}
+
+fn changes(tips: &[&str], limit: Option<usize>) -> crate::Result<Vec<(gix_hash::ObjectId, Vec<BString>)>> {
+    let odb = odb();
+    let mut graph = gix_revision::Graph::<()>::new(&odb, None);
+    let mut out = gix_revision::walk_with_changes(tips.iter().map(|tip| hex_to_id(tip)), &mut graph, &odb, limit)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(graph.detach().is_empty(), "the graph is only used for lookups");
+    out.sort();
+    Ok(out)
+}
+
+fn paths(paths: &[&str]) -> Vec<BString> {
+    paths.iter().map(|p| (*p).into()).collect()
+}
+
+#[test]
+fn paths_changed_compared_to_first_parent() -> crate::Result {
+    let merge = "6b6323261b72096d99904a1ebb54277bd9733672";
+    let mut expected = vec![
+        (hex_to_id(merge), paths(&["side"])),
+        (hex_to_id("12feec12e5a58ce6f2df14966dc445d470655925"), paths(&["side"])),
+        (
+            hex_to_id("03069805c6ccb12cb629b01a4fe4cbfcc8c9ea85"),
+            paths(&["a", "c", "dir/b"]),
+        ),
+        (hex_to_id("a7753b6e197662c75abad93d0069df365323903b"), paths(&["dir/b"])),
+        (hex_to_id("691be7cce350858b358e5aed0061f1117be57cfe"), paths(&["a"])),
+    ];
+    expected.sort();
+    let mut actual = changes(&[merge], None)?;
+    for (_id, paths) in &mut actual {
+        paths.sort();
+    }
+    assert_eq!(
+        actual, expected,
+        "each commit is seen once, the merge is compared to its first parent, and the root to the empty tree"
+    );
+    Ok(())
+}
+
+#[test]
+fn limit_bounds_the_walk() -> crate::Result {
+    let tip = "03069805c6ccb12cb629b01a4fe4cbfcc8c9ea85";
+    assert_eq!(changes(&[tip], Some(1))?.len(), 1);
+    assert_eq!(
+        changes(&[tip], Some(10))?.len(),
+        3,
+        "the limit is just an upper bound for the amount of returned commits"
+    );
+    assert!(changes(&[tip], Some(0))?.is_empty());
+    Ok(())
+}
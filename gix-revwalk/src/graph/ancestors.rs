@@ -0,0 +1,259 @@
+use gix_date::SecondsSinceUnixEpoch;
+use gix_hash::ObjectId;
+use smallvec::SmallVec;
+
+use crate::{
+    graph::{try_lookup_or_insert_default, Commit, Generation},
+    Graph, PriorityQueue,
+};
+
+/// The error returned by [`ancestors()`](crate::Graph::ancestors()) and its [`Iter`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Lookup(#[from] try_lookup_or_insert_default::Error),
+}
+
+/// Specify the order in which [`ancestors()`](crate::Graph::ancestors()) returns commits.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Sorting {
+    /// Return commits youngest first by committer time, across all tips.
+    ///
+    /// Commits are returned as they are discovered, but a parent is returned before its child if
+    /// it has a younger commit time due to clock skew.
+    #[default]
+    ByCommitTime,
+    /// Return commits youngest first by committer time, but never return a commit before all of its children,
+    /// similar to `git log --date-order`.
+    ///
+    /// This requires all reachable commits to be discovered before the first one can be returned.
+    DateOrder,
+    /// Never return a commit before all of its children, and don't interleave lines of history,
+    /// similar to `git log --topo-order`.
+    ///
+    /// Commits without children are returned youngest first by committer time, each followed by as much of its history
+    /// as possible before the next line of history is returned. After a merge, the history of the last parent is returned first.
+    /// Unlike with [`DateOrder`](Self::DateOrder), commit times are only used to order these lines of history.
+    ///
+    /// This requires all reachable commits to be discovered before the first one can be returned.
+    TopoOrder,
+}
+
+/// An iterator over the ids of all commits reachable from a set of tips, created by [`ancestors()`](crate::Graph::ancestors()).
+///
+/// The data associated with each returned commit can be obtained with [`get()`](Self::get()).
+pub struct Iter<'graph, 'find, T> {
+    graph: &'graph mut Graph<'find, Commit<T>>,
+    min_generation: Option<Generation>,
+    queue: PriorityQueue<SecondsSinceUnixEpoch, ObjectId>,
+    state: State,
+}
+
+enum State {
+    ByCommitTime {
+        seen: gix_hashtable::HashSet<ObjectId>,
+    },
+    DateOrder {
+        /// The amount of children that weren't returned yet, for each reachable commit.
+        pending_children: gix_hashtable::HashMap<ObjectId, usize>,
+    },
+    TopoOrder {
+        /// The amount of children that weren't returned yet, for each reachable commit.
+        pending_children: gix_hashtable::HashMap<ObjectId, usize>,
+        /// The commits to return next, last one first, which are used instead of the queue.
+        stack: Vec<ObjectId>,
+    },
+}
+
+/// Traversal
+impl<'find, T: Default> Graph<'find, Commit<T>> {
+    /// Walk all commits reachable from `tips` lazily and return an iterator over their ids in the given `sorting` order,
+    /// similar to `git rev-list tip1 tip2`. Commits reachable from multiple tips are returned only once.
+    ///
+    /// If `min_generation` is set, commits whose generation is lower aren't returned and their parents aren't traversed.
+    /// Note that generation numbers are only known for commits in the commit-graph, and other commits are never cut off.
+    ///
+    /// Each returned commit is inserted into the graph with default data if it wasn't present yet, which keeps its parents,
+    /// commit time and generation so that subsequent walks don't need to access the object database for it again.
+    /// Tips and parents that don't exist, like in shallow clones, are skipped.
+    pub fn ancestors(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        sorting: Sorting,
+        min_generation: Option<Generation>,
+    ) -> Result<Iter<'_, 'find, T>, Error> {
+        let mut iter = Iter {
+            graph: self,
+            min_generation,
+            queue: PriorityQueue::new(),
+            state: State::ByCommitTime {
+                seen: Default::default(),
+            },
+        };
+        let tips: Vec<ObjectId> = tips.into_iter().map(Into::into).collect();
+        match sorting {
+            Sorting::ByCommitTime => {
+                for tip in tips {
+                    iter.enqueue(tip)?;
+                }
+            }
+            Sorting::DateOrder | Sorting::TopoOrder => iter.collect_reachable(tips, sorting)?,
+        }
+        Ok(iter)
+    }
+
+    /// Walk all commits reachable from `tips` and return an iterator over their ids, youngest first by committer time
+    /// across all tips, similar to `git log tip1 tip2`. Commits reachable from multiple tips are returned only once.
+    ///
+    /// This is a shortcut for [`ancestors()`](Self::ancestors()) with [`Sorting::ByCommitTime`] and without generation cutoff.
+    pub fn walk_tips_time_ordered(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Iter<'_, 'find, T>, Error> {
+        self.ancestors(tips, Sorting::ByCommitTime, None)
+    }
+
+    /// Return the ids of all commits reachable from `tips`, youngest first by committer time, but with the guarantee that
+    /// no commit is returned before all of its children, just like `git log --date-order tip1 tip2`.
+    ///
+    /// This is a shortcut for collecting [`ancestors()`](Self::ancestors()) with [`Sorting::DateOrder`].
+    pub fn log_order_walk(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        self.ancestors(tips, Sorting::DateOrder, None)?.collect()
+    }
+
+    /// Return the ids of all commits reachable from `tips` such that no commit is returned before all of its children,
+    /// and such that lines of history aren't interleaved, just like `git log --topo-order tip1 tip2`.
+    ///
+    /// This is a shortcut for collecting [`ancestors()`](Self::ancestors()) with [`Sorting::TopoOrder`].
+    pub fn topo_order(&mut self, tips: impl IntoIterator<Item = impl Into<ObjectId>>) -> Result<Vec<ObjectId>, Error> {
+        self.ancestors(tips, Sorting::TopoOrder, None)?.collect()
+    }
+}
+
+impl<'graph, 'find, T: Default> Iter<'graph, 'find, T> {
+    /// Return the commit data associated with `id`, which is available for all commits returned so far.
+    pub fn get(&self, id: &gix_hash::oid) -> Option<&Commit<T>> {
+        self.graph.get(id)
+    }
+
+    /// Return the commit named `id` after inserting it into the graph,
+    /// or `None` if it doesn't exist or is lower than our minimal generation.
+    fn lookup(&mut self, id: ObjectId) -> Result<Option<&Commit<T>>, Error> {
+        let min_generation = self.min_generation;
+        let is_too_old = |commit: &Commit<T>| matches!((commit.generation, min_generation), (Some(generation), Some(min)) if generation < min);
+        Ok(self
+            .graph
+            .try_lookup_or_insert_commit(id, |_| {})?
+            .map(|commit| &*commit)
+            .filter(|commit| !is_too_old(commit)))
+    }
+
+    fn enqueue(&mut self, id: ObjectId) -> Result<(), Error> {
+        let seen = match &mut self.state {
+            State::ByCommitTime { seen } => seen,
+            State::DateOrder { .. } | State::TopoOrder { .. } => {
+                unreachable!("only used when sorting by commit time")
+            }
+        };
+        if !seen.insert(id) {
+            return Ok(());
+        }
+        if let Some(commit) = self.lookup(id)? {
+            let time = commit.commit_time;
+            self.queue.insert(time, id);
+        }
+        Ok(())
+    }
+
+    fn collect_reachable(&mut self, tips: Vec<ObjectId>, sorting: Sorting) -> Result<(), Error> {
+        let mut reachable = gix_hashtable::HashMap::<ObjectId, usize>::default();
+        let mut to_visit = tips;
+        while let Some(id) = to_visit.pop() {
+            if reachable.contains_key(&id) {
+                continue;
+            }
+            if let Some(commit) = self.lookup(id)? {
+                to_visit.extend(commit.parents.iter().copied());
+                reachable.insert(id, 0);
+            }
+        }
+        for id in reachable.keys().copied().collect::<Vec<_>>() {
+            for parent_id in &self.graph[&id].parents {
+                if let Some(count) = reachable.get_mut(parent_id) {
+                    *count += 1;
+                }
+            }
+        }
+        let childless = reachable
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| (self.graph[id].commit_time, *id));
+        self.state = match sorting {
+            Sorting::ByCommitTime => unreachable!("commits sorted by time are returned as they are discovered"),
+            Sorting::DateOrder => {
+                self.queue.extend(childless);
+                State::DateOrder {
+                    pending_children: reachable,
+                }
+            }
+            Sorting::TopoOrder => {
+                let mut childless: Vec<_> = childless.collect();
+                childless.sort();
+                State::TopoOrder {
+                    stack: childless.into_iter().map(|(_, id)| id).collect(),
+                    pending_children: reachable,
+                }
+            }
+        };
+        Ok(())
+    }
+}
+
+impl<'graph, 'find, T: Default> Iterator for Iter<'graph, 'find, T> {
+    type Item = Result<ObjectId, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = match &mut self.state {
+            State::TopoOrder { stack, .. } => stack.pop(),
+            State::ByCommitTime { .. } | State::DateOrder { .. } => self.queue.pop_value(),
+        }?;
+        let parents: SmallVec<[_; 2]> = self.graph[&id].parents.iter().copied().collect();
+        match &mut self.state {
+            State::ByCommitTime { .. } => {
+                for parent_id in parents {
+                    if let Err(err) = self.enqueue(parent_id) {
+                        return Some(Err(err));
+                    }
+                }
+            }
+            State::DateOrder { pending_children } => {
+                for parent_id in parents {
+                    if let Some(count) = pending_children.get_mut(&parent_id) {
+                        *count -= 1;
+                        if *count == 0 {
+                            self.queue.insert(self.graph[&parent_id].commit_time, parent_id);
+                        }
+                    }
+                }
+            }
+            State::TopoOrder {
+                pending_children,
+                stack,
+            } => {
+                for parent_id in parents {
+                    if let Some(count) = pending_children.get_mut(&parent_id) {
+                        *count -= 1;
+                        if *count == 0 {
+                            stack.push(parent_id);
+                        }
+                    }
+                }
+            }
+        }
+        Some(Ok(id))
+    }
+}
@@ -0,0 +1,124 @@
+use gix_hash::ObjectId;
+
+use crate::{
+    graph::{commit::to_owned, try_lookup, Commit},
+    Graph,
+};
+
+/// The error returned by [`ancestors_parallel()`](crate::Graph::ancestors_parallel()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Lookup(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    ToOwned(#[from] to_owned::Error),
+}
+
+/// The minimal amount of commits to decode per thread, as it's not worth spawning threads for less.
+const MIN_CHUNK_SIZE: usize = 32;
+
+/// Parallel traversal
+impl<'find, T: Default> Graph<'find, Commit<T>> {
+    /// Return the ids of all commits reachable from `tips`, like [`ancestors()`](Self::ancestors()), but decode the commits
+    /// of independent lines of history using up to `thread_limit` threads, or as many threads as there are logical cores if `None`.
+    ///
+    /// As the graph's own object database handle can't be shared among threads, `objects` is used instead, which must be
+    /// a handle to the same object database and is cloned once per thread.
+    ///
+    /// Commits are returned breadth-first, that is tips first, followed by their parents, grandparents, and so on.
+    /// This order as well as the commits and data inserted into the graph are the same no matter how many threads are used,
+    /// as threads only decode commits while the traversal itself happens on the calling thread.
+    /// Note that threads are only used for lines of history that are wide enough, and linear history is walked on the calling thread.
+    ///
+    /// Each returned commit is inserted into the graph with default data if it wasn't present yet, and commits that are
+    /// present already aren't decoded again.
+    /// Tips and parents that don't exist, like in shallow clones, are skipped.
+    pub fn ancestors_parallel<Find>(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        objects: Find,
+        thread_limit: Option<usize>,
+    ) -> Result<Vec<ObjectId>, Error>
+    where
+        Find: gix_object::Find + Clone + Send,
+    {
+        let num_threads = gix_features::parallel::num_threads(thread_limit);
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut frontier: Vec<ObjectId> = tips.into_iter().map(Into::into).filter(|id| seen.insert(*id)).collect();
+        let mut out = Vec::new();
+        while !frontier.is_empty() {
+            let unknown: Vec<_> = frontier
+                .iter()
+                .filter(|id| !self.map.contains_key(*id))
+                .copied()
+                .collect();
+            let cache = self.cache.as_ref();
+            let chunk_size = (unknown.len() / num_threads).max(MIN_CHUNK_SIZE);
+            let decoded = if unknown.len() <= chunk_size {
+                decode(&unknown, &objects, cache)?
+            } else {
+                gix_features::parallel::threads(|scope| -> Result<Vec<_>, Error> {
+                    let threads: Vec<_> = unknown
+                        .chunks(chunk_size)
+                        .enumerate()
+                        .map(|(thread_id, ids)| {
+                            let objects = objects.clone();
+                            gix_features::parallel::build_thread()
+                                .name(format!("gix_revwalk::ancestors_parallel({thread_id})"))
+                                .spawn_scoped(scope, move || decode(ids, &objects, cache))
+                                .expect("valid name")
+                        })
+                        .collect();
+                    let mut decoded = Vec::with_capacity(unknown.len());
+                    for thread in threads {
+                        decoded.extend(thread.join().expect("no panic")?);
+                    }
+                    Ok(decoded)
+                })?
+            };
+            for (id, commit) in unknown.into_iter().zip(decoded) {
+                if let Some(commit) = commit {
+                    self.map.insert(
+                        id,
+                        Commit {
+                            parents: commit.parents,
+                            commit_time: commit.commit_time,
+                            generation: commit.generation,
+                            data: T::default(),
+                        },
+                    );
+                }
+            }
+
+            let mut next_frontier = Vec::new();
+            for id in frontier {
+                let commit = match self.map.get(&id) {
+                    Some(commit) => commit,
+                    None => continue,
+                };
+                out.push(id);
+                next_frontier.extend(commit.parents.iter().copied().filter(|id| seen.insert(*id)));
+            }
+            frontier = next_frontier;
+        }
+        Ok(out)
+    }
+}
+
+/// Decode the commits named `ids` in order, with `None` for commits that don't exist.
+fn decode(
+    ids: &[ObjectId],
+    objects: &dyn gix_object::Find,
+    cache: Option<&gix_commitgraph::Graph>,
+) -> Result<Vec<Option<Commit<()>>>, Error> {
+    let mut buf = Vec::new();
+    ids.iter()
+        .map(|id| {
+            Ok(match try_lookup(id, objects, cache, &mut buf)? {
+                Some(commit) => Some(commit.to_owned(|| ())?),
+                None => None,
+            })
+        })
+        .collect()
+}
@@ -25,6 +25,16 @@ impl<'graph> LazyCommit<'graph> {
         })
     }
 
+    /// Returns the id of the tree this commit points to.
+    ///
+    /// Note that this can only fail if the commit is backed by the object database *and* parsing fails.
+    pub fn tree_id(&self) -> Result<gix_hash::ObjectId, gix_object::decode::Error> {
+        Ok(match &self.backing {
+            Either::Left(buf) => gix_object::CommitRefIter::from_bytes(buf).tree_id()?,
+            Either::Right((cache, pos)) => cache.commit_at(*pos).root_tree_id().to_owned(),
+        })
+    }
+
     /// Returns the generation of the commit if it is backed by a commit graph.
     pub fn generation(&self) -> Option<Generation> {
         match &self.backing {
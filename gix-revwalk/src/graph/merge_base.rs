@@ -0,0 +1,311 @@
+use gix_date::SecondsSinceUnixEpoch;
+use gix_hash::{oid, ObjectId};
+use smallvec::SmallVec;
+
+use crate::{
+    graph::{commit::iter_parents, Generation},
+    Graph, PriorityQueue,
+};
+
+/// The error returned by [`merge_base()`](crate::Graph::merge_base()), [`merge_bases()`](crate::Graph::merge_bases()),
+/// [`count_ahead_behind()`](crate::Graph::count_ahead_behind()) and [`count_reachable()`](crate::Graph::count_reachable()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Lookup(#[from] gix_object::find::existing_iter::Error),
+    #[error("A commit could not be decoded during traversal")]
+    Decode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Parent(#[from] iter_parents::Error),
+}
+
+type Flags = u8;
+/// The commit is reachable from the first commit.
+const PARENT1: Flags = 1 << 0;
+/// The commit is reachable from one of the other commits.
+const PARENT2: Flags = 1 << 1;
+/// The commit is reachable from a common ancestor, and thus can't be a merge base itself.
+const STALE: Flags = 1 << 2;
+/// The commit was already added to the result.
+const RESULT: Flags = 1 << 3;
+
+/// Commits that aren't in the commit-graph are considered to be younger than all commits in it, just like `git` does.
+const GENERATION_NUMBER_INFINITY: Generation = Generation::MAX;
+
+type FlagsById = gix_hashtable::HashMap<ObjectId, Flags>;
+type Queue = PriorityQueue<(Generation, SecondsSinceUnixEpoch), ObjectId>;
+
+/// Determine when [`paint_down_to_common()`](Graph::paint_down_to_common()) stops.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Finish {
+    /// Stop as soon as all queued commits are reachable from a common ancestor, which suffices to find merge bases.
+    AllStale,
+    /// Keep going until no queued commit can be an ancestor of a commit that was reached from only one side,
+    /// so that commits which are reachable from both sides aren't counted.
+    Counting,
+}
+
+/// Merge bases
+impl<'find, T> Graph<'find, T> {
+    /// Return the best common ancestor of `first` and `second`, similar to `git merge-base <first> <second>`,
+    /// or `None` if they don't have common history.
+    ///
+    /// If there are multiple merge bases, for instance after criss-cross merges, the one with the youngest commit is returned.
+    /// Use [`merge_bases()`](Self::merge_bases()) to obtain all of them.
+    /// Commits that don't exist, like in shallow clones, are skipped, and the graph itself remains unchanged.
+    pub fn merge_base(&mut self, first: &oid, second: &oid) -> Result<Option<ObjectId>, Error> {
+        Ok(self
+            .merge_bases_of_two(first.to_owned(), second.to_owned())?
+            .into_iter()
+            .next())
+    }
+
+    /// Return all merge bases of `commits`, i.e. the common ancestors of all `commits` which aren't reachable from another
+    /// common ancestor, youngest first, similar to `git merge-base --octopus --all`.
+    ///
+    /// With two commits, this is the same as `git merge-base --all` and typically yields a single commit, but may yield more
+    /// after criss-cross merges. If `commits` doesn't have common history, the result is empty.
+    ///
+    /// If available, generation numbers from the commit-graph are used to order the traversal and to stop it early.
+    /// Commits that don't exist, like in shallow clones, are skipped, and the graph itself remains unchanged.
+    pub fn merge_bases(&mut self, commits: &[ObjectId]) -> Result<Vec<ObjectId>, Error> {
+        let (first, rest) = match commits.split_first() {
+            Some(split) => split,
+            None => return Ok(Vec::new()),
+        };
+        let mut bases = vec![*first];
+        for commit in rest {
+            let mut next_bases = Vec::new();
+            for base in &bases {
+                for id in self.merge_bases_of_two(*commit, *base)? {
+                    if !next_bases.contains(&id) {
+                        next_bases.push(id);
+                    }
+                }
+            }
+            bases = self.remove_redundant(next_bases)?;
+            if bases.is_empty() {
+                break;
+            }
+        }
+        self.sort_youngest_first(&mut bases)?;
+        Ok(bases)
+    }
+
+    /// Return the amount of commits that are reachable from `local` but not from `upstream`, and the amount of commits that
+    /// are reachable from `upstream` but not from `local`, similar to `git rev-list --count --left-right <local>...<upstream>`.
+    ///
+    /// These are the amount of commits `local` is ahead of and behind `upstream`, and both are 0 if they are the same commit.
+    /// Generation numbers from the commit-graph are used to traverse in topological order if available, so only commits up
+    /// to the merge bases are traversed. Without them, the traversal continues past the merge bases to correct for clock skew.
+    /// Commits that don't exist, like in shallow clones, are skipped, and the graph itself remains unchanged.
+    pub fn count_ahead_behind(&mut self, local: &oid, upstream: &oid) -> Result<(usize, usize), Error> {
+        let mut flags = FlagsById::default();
+        self.paint_down_to_common(
+            &[local.to_owned()],
+            &[upstream.to_owned()],
+            0,
+            Finish::Counting,
+            &mut flags,
+        )?;
+        Ok((count_only(&flags, PARENT1), count_only(&flags, PARENT2)))
+    }
+
+    /// Return the amount of commits that are reachable from `tips` but not from any of the `hidden` commits,
+    /// similar to `git rev-list --count tip1 tip2 ^hidden1 ^hidden2`.
+    ///
+    /// Commits that don't exist, like in shallow clones, are skipped, and the graph itself remains unchanged.
+    pub fn count_reachable(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        hidden: &[ObjectId],
+    ) -> Result<usize, Error> {
+        let tips: Vec<ObjectId> = tips.into_iter().map(Into::into).collect();
+        let mut flags = FlagsById::default();
+        self.paint_down_to_common(&tips, hidden, 0, Finish::Counting, &mut flags)?;
+        Ok(count_only(&flags, PARENT1))
+    }
+
+    fn merge_bases_of_two(&mut self, one: ObjectId, two: ObjectId) -> Result<Vec<ObjectId>, Error> {
+        if one == two {
+            return Ok(if self.try_lookup(&one)?.is_some() {
+                vec![one]
+            } else {
+                Vec::new()
+            });
+        }
+        let mut flags = FlagsById::default();
+        let bases = self.paint_down_to_common(&[one], &[two], 0, Finish::AllStale, &mut flags)?;
+        let bases = bases.into_iter().filter(|id| flags[id] & STALE == 0).collect();
+        let mut bases = self.remove_redundant(bases)?;
+        self.sort_youngest_first(&mut bases)?;
+        Ok(bases)
+    }
+
+    /// Mark all commits reachable from `ones` with `PARENT1`, and all commits reachable from `twos` with `PARENT2`,
+    /// and return the commits that were reached from both first, in traversal order.
+    /// Commits with a generation lower than `min_generation` aren't traversed.
+    ///
+    /// Without `min_generation` and with [`Finish::Counting`], all commits that are reachable from only one side
+    /// are marked accordingly when done.
+    fn paint_down_to_common(
+        &mut self,
+        ones: &[ObjectId],
+        twos: &[ObjectId],
+        min_generation: Generation,
+        finish: Finish,
+        flags: &mut FlagsById,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut queue = Queue::new();
+        for one in ones {
+            self.paint(*one, PARENT1, flags, &mut queue)?;
+        }
+        for two in twos {
+            self.paint(*two, PARENT2, flags, &mut queue)?;
+        }
+
+        let mut out = Vec::new();
+        let mut min_one_sided_generation = None;
+        loop {
+            if !queue.iter_unordered().any(|id| flags[id] & STALE == 0) {
+                if finish == Finish::AllStale {
+                    break;
+                }
+                // Without generation numbers, clock skew can make us reach a commit from one side before reaching it
+                // from a common ancestor, so `STALE` has to be propagated to all commits which could still be reached.
+                let min_one_sided_generation = match min_one_sided_generation {
+                    Some(generation) => generation,
+                    None => match self.min_one_sided_generation(flags)? {
+                        Some(generation) => *min_one_sided_generation.insert(generation),
+                        None => break,
+                    },
+                };
+                if !matches!(queue.peek(), Some(((generation, _), _)) if *generation >= min_one_sided_generation) {
+                    break;
+                }
+            }
+            let ((generation, _), id) = queue.pop().expect("at least one item");
+            if generation < min_generation {
+                break;
+            }
+            let commit_flags = flags.get_mut(&id).expect("painted before queueing");
+            let mut parent_flags = *commit_flags & (PARENT1 | PARENT2 | STALE);
+            if parent_flags == PARENT1 | PARENT2 {
+                if *commit_flags & RESULT == 0 {
+                    *commit_flags |= RESULT;
+                    out.push(id);
+                }
+                parent_flags |= STALE;
+            }
+            let parents = self
+                .lookup(&id)?
+                .iter_parents()
+                .collect::<Result<SmallVec<[_; 2]>, _>>()?;
+            for parent_id in parents {
+                if matches!(flags.get(&parent_id), Some(flags) if flags & parent_flags == parent_flags) {
+                    continue;
+                }
+                self.paint(parent_id, parent_flags, flags, &mut queue)?;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Return the lowest generation of all commits in `flags` that were reached from only one side,
+    /// or `None` if there is no such commit.
+    ///
+    /// Commits with a lower generation can't be ancestors of these, which makes them irrelevant for counting.
+    fn min_one_sided_generation(&mut self, flags: &FlagsById) -> Result<Option<Generation>, Error> {
+        let mut min_generation = None;
+        for (id, flags) in flags {
+            if flags & (PARENT1 | PARENT2) == PARENT1 | PARENT2 {
+                continue;
+            }
+            if let Some(commit) = self.try_lookup(id)? {
+                let generation = commit.generation().unwrap_or(GENERATION_NUMBER_INFINITY);
+                min_generation = Some(min_generation.map_or(generation, |min: Generation| min.min(generation)));
+            }
+        }
+        Ok(min_generation)
+    }
+
+    /// Add `new_flags` to `id` and queue it, unless it doesn't exist.
+    fn paint(&mut self, id: ObjectId, new_flags: Flags, flags: &mut FlagsById, queue: &mut Queue) -> Result<(), Error> {
+        let commit = match self.try_lookup(&id)? {
+            Some(commit) => commit,
+            None => return Ok(()),
+        };
+        let key = (
+            commit.generation().unwrap_or(GENERATION_NUMBER_INFINITY),
+            commit.committer_timestamp()?,
+        );
+        *flags.entry(id).or_default() |= new_flags;
+        queue.insert(key, id);
+        Ok(())
+    }
+
+    /// Remove all `candidates` that are reachable from other `candidates`, keeping the order of the remaining ones.
+    fn remove_redundant(&mut self, candidates: Vec<ObjectId>) -> Result<Vec<ObjectId>, Error> {
+        if candidates.len() < 2 {
+            return Ok(candidates);
+        }
+        let mut min_generation = GENERATION_NUMBER_INFINITY;
+        for id in &candidates {
+            if let Some(commit) = self.try_lookup(id)? {
+                min_generation = min_generation.min(commit.generation().unwrap_or(GENERATION_NUMBER_INFINITY));
+            }
+        }
+        // Without generation numbers, we can't know where to stop.
+        if min_generation == GENERATION_NUMBER_INFINITY {
+            min_generation = 0;
+        }
+
+        let mut redundant = vec![false; candidates.len()];
+        for (idx, candidate) in candidates.iter().enumerate() {
+            if redundant[idx] {
+                continue;
+            }
+            let others: Vec<_> = candidates
+                .iter()
+                .zip(&redundant)
+                .enumerate()
+                .filter_map(|(other_idx, (id, is_redundant))| (other_idx != idx && !is_redundant).then_some(*id))
+                .collect();
+            let mut flags = FlagsById::default();
+            self.paint_down_to_common(&[*candidate], &others, min_generation, Finish::AllStale, &mut flags)?;
+            if matches!(flags.get(candidate), Some(flags) if flags & PARENT2 != 0) {
+                redundant[idx] = true;
+            }
+            for (other_idx, other) in candidates.iter().enumerate() {
+                if other_idx != idx && matches!(flags.get(other), Some(flags) if flags & PARENT1 != 0) {
+                    redundant[other_idx] = true;
+                }
+            }
+        }
+        Ok(candidates
+            .into_iter()
+            .zip(redundant)
+            .filter_map(|(id, is_redundant)| (!is_redundant).then_some(id))
+            .collect())
+    }
+
+    fn sort_youngest_first(&mut self, ids: &mut [ObjectId]) -> Result<(), Error> {
+        let mut times = gix_hashtable::HashMap::default();
+        for id in ids.iter() {
+            if let Some(commit) = self.try_lookup(id)? {
+                times.insert(*id, commit.committer_timestamp()?);
+            }
+        }
+        ids.sort_by(|a, b| times.get(b).cmp(&times.get(a)));
+        Ok(())
+    }
+}
+
+/// Count the commits that were marked with `side`, but not with the other side.
+fn count_only(flags: &FlagsById, side: Flags) -> usize {
+    flags
+        .values()
+        .filter(|flags| **flags & (PARENT1 | PARENT2) == side)
+        .count()
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Formatter, ops::Index};
+use std::{collections::VecDeque, fmt::Formatter, ops::Index};
 
 use gix_hash::oid;
 use smallvec::SmallVec;
@@ -8,9 +8,19 @@ use crate::Graph;
 /// A mapping between an object id and arbitrary data, and produced when calling [`Graph::detach()`].
 pub type IdMap<T> = gix_hashtable::HashMap<gix_hash::ObjectId, T>;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod ancestors;
+///
+#[cfg(feature = "parallel")]
+#[allow(clippy::empty_docs)]
+pub mod ancestors_parallel;
 ///
 #[allow(clippy::empty_docs)]
 pub mod commit;
+///
+#[allow(clippy::empty_docs)]
+pub mod merge_base;
 
 mod errors {
     ///
@@ -31,6 +41,56 @@ mod errors {
         }
     }
 
+    ///
+    #[allow(clippy::empty_docs)]
+    pub mod commit_time {
+        /// The error returned by [`commit_time()`](crate::Graph::commit_time()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            Lookup(#[from] gix_object::find::existing_iter::Error),
+            #[error("A commit could not be decoded to obtain its commit time")]
+            Decode(#[from] gix_object::decode::Error),
+        }
+    }
+
+    ///
+    #[allow(clippy::empty_docs)]
+    pub mod earliest_commit {
+        use crate::graph::commit::iter_parents;
+
+        /// The error returned by [`earliest_commit()`](crate::Graph::earliest_commit()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            Lookup(#[from] gix_object::find::existing_iter::Error),
+            #[error("A commit could not be decoded during traversal")]
+            Decode(#[from] gix_object::decode::Error),
+            #[error(transparent)]
+            Parent(#[from] iter_parents::Error),
+        }
+    }
+
+    ///
+    #[allow(clippy::empty_docs)]
+    pub mod lookup_parents {
+        use crate::graph::commit::iter_parents;
+
+        /// The error returned by methods that only look up commits to obtain their parents, like
+        /// [`parent_count()`](crate::Graph::parent_count()), [`reachable_from()`](crate::Graph::reachable_from()),
+        /// [`ancestors_among()`](crate::Graph::ancestors_among()) and [`is_ancestor()`](crate::Graph::is_ancestor()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            Lookup(#[from] gix_object::find::existing_iter::Error),
+            #[error(transparent)]
+            Parent(#[from] iter_parents::Error),
+        }
+    }
+
     ///
     #[allow(clippy::empty_docs)]
     pub mod try_lookup_or_insert_default {
@@ -47,7 +107,7 @@ mod errors {
         }
     }
 }
-pub use errors::{insert_parents, try_lookup_or_insert_default};
+pub use errors::{commit_time, earliest_commit, insert_parents, lookup_parents, try_lookup_or_insert_default};
 use gix_date::SecondsSinceUnixEpoch;
 
 /// The generation away from the HEAD of graph, useful to limit algorithms by topological depth as well.
@@ -98,15 +158,26 @@ impl<'find, T> Graph<'find, T> {
         self.map.insert(id, value)
     }
 
-    /// Remove all data from the graph to start over.
+    /// Remove all data from the graph to start over, including the [shallow boundary](Self::shallow_boundary()).
     pub fn clear(&mut self) {
         self.map.clear();
+        self.shallow_boundary.clear();
+    }
+
+    /// Return the commits whose parents were found to be missing while inserting them with [`insert_parents()`](Self::insert_parents()).
+    ///
+    /// In a shallow clone, these are the commits at its boundary, whose history was cut off.
+    /// In a complete repository, this is always empty unless commits are missing in the object database.
+    pub fn shallow_boundary(&self) -> &gix_hashtable::HashSet<gix_hash::ObjectId> {
+        &self.shallow_boundary
     }
 
     /// Insert the parents of commit named `id` to the graph and associate new parents with data
     /// by calling `new_parent_data(parent_id, committer_timestamp)`, or update existing parents
     /// data with `update_existing(parent_id, &mut existing_data)`.
     /// If `first_parent` is `true`, only the first parent of commits will be looked at.
+    ///
+    /// Parents that don't exist, like in shallow clones, are skipped, and `id` is added to the [shallow boundary](Self::shallow_boundary()).
     pub fn insert_parents(
         &mut self,
         id: &gix_hash::oid,
@@ -122,7 +193,11 @@ impl<'find, T> Graph<'find, T> {
                 gix_hashtable::hash_map::Entry::Vacant(entry) => {
                     let parent = match try_lookup(&parent_id, &*self.find, self.cache.as_ref(), &mut self.parent_buf)? {
                         Some(p) => p,
-                        None => continue, // skip missing objects, this is due to shallow clones for instance.
+                        None => {
+                            // skip missing objects, this is due to shallow clones for instance.
+                            self.shallow_boundary.insert(id.to_owned());
+                            continue;
+                        }
                     };
 
                     let parent_commit_date = parent.committer_timestamp().unwrap_or_default();
@@ -139,6 +214,209 @@ impl<'find, T> Graph<'find, T> {
         Ok(())
     }
 
+    /// Return the amount of parents of the commit named `id`, which is more than 1 for merge commits and 0 for root commits.
+    ///
+    /// Only the parent information is decoded, and if a commit-graph is available it's used instead of the object database.
+    pub fn parent_count(&mut self, id: &gix_hash::oid) -> Result<usize, lookup_parents::Error> {
+        let mut count = 0;
+        for parent_id in self.lookup(id)?.iter_parents() {
+            parent_id?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Return the id and committer time of the oldest commit reachable from `tips` by committer time,
+    /// or `None` if none of the `tips` exist. If `limit` is `Some(n)`, no more than `n` commits will be looked at.
+    ///
+    /// Note that all reachable commits have to be visited as the oldest commit can be anywhere in the history.
+    /// Due to clock skew, the oldest commit by time isn't necessarily a root commit either.
+    /// Commits that don't exist, like in shallow clones, are skipped, and if multiple commits share the oldest time,
+    /// the one with the smallest id is returned.
+    /// The graph itself remains unchanged.
+    pub fn earliest_commit(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+        limit: Option<usize>,
+    ) -> Result<Option<(gix_hash::ObjectId, gix_date::Time)>, earliest_commit::Error> {
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut queue: VecDeque<_> = tips.into_iter().map(Into::into).filter(|id| seen.insert(*id)).collect();
+        let mut remaining = limit.unwrap_or(usize::MAX);
+        let mut earliest = None;
+        while let Some(id) = queue.pop_front() {
+            if remaining == 0 {
+                break;
+            }
+            let commit = match try_lookup(&id, &*self.find, self.cache.as_ref(), &mut self.buf)? {
+                Some(commit) => commit,
+                None => continue,
+            };
+            remaining -= 1;
+            let commit_time = commit.committer_timestamp()?;
+            if earliest.map_or(true, |earliest| (commit_time, id) < earliest) {
+                earliest = Some((commit_time, id));
+            }
+            for parent_id in commit.iter_parents() {
+                let parent_id = parent_id?;
+                if seen.insert(parent_id) {
+                    queue.push_back(parent_id);
+                }
+            }
+        }
+
+        let (seconds, id) = match earliest {
+            Some(earliest) => earliest,
+            None => return Ok(None),
+        };
+        // The commit-graph doesn't store the time-zone offset, so get the full time from the object database if possible.
+        let time = match self
+            .find
+            .try_find(&id, &mut self.buf)
+            .map_err(gix_object::find::existing_iter::Error::Find)?
+        {
+            Some(data) if data.kind.is_commit() => gix_object::CommitRefIter::from_bytes(data.data).committer()?.time,
+            _ => gix_date::Time::new(seconds, 0),
+        };
+        Ok(Some((id, time)))
+    }
+
+    /// Return the ids of all commits reachable from `tips`, including the `tips` themselves.
+    ///
+    /// The parents of commits in `shallow_commits` aren't followed, which is useful to stop at the boundary of shallow clones
+    /// as recorded in `.git/shallow`, and an empty slice traverses everything.
+    /// Commits that don't exist are skipped and not part of the returned set.
+    /// The graph itself remains unchanged.
+    pub fn reachable_from(
+        &mut self,
+        tips: impl IntoIterator<Item = impl Into<gix_hash::ObjectId>>,
+        shallow_commits: &[gix_hash::ObjectId],
+    ) -> Result<gix_hashtable::HashSet<gix_hash::ObjectId>, lookup_parents::Error> {
+        let shallow_commits: gix_hashtable::HashSet<_> = shallow_commits.iter().copied().collect();
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut queue: VecDeque<_> = tips.into_iter().map(Into::into).filter(|id| seen.insert(*id)).collect();
+        let mut reachable = gix_hashtable::HashSet::default();
+        while let Some(id) = queue.pop_front() {
+            let commit = match try_lookup(&id, &*self.find, self.cache.as_ref(), &mut self.buf)? {
+                Some(commit) => commit,
+                None => continue,
+            };
+            reachable.insert(id);
+            if shallow_commits.contains(&id) {
+                continue;
+            }
+            for parent_id in commit.iter_parents() {
+                let parent_id = parent_id?;
+                if seen.insert(parent_id) {
+                    queue.push_back(parent_id);
+                }
+            }
+        }
+        Ok(reachable)
+    }
+
+    /// Return the subset of `candidates` that are reachable from `source`, including `source` itself if it is a candidate,
+    /// similar to calling `git merge-base --is-ancestor <candidate> <source>` for each candidate.
+    ///
+    /// This is done in a single walk that stops as soon as all candidates were found, instead of one walk per candidate.
+    /// Commits that don't exist are skipped, and the graph itself remains unchanged.
+    pub fn ancestors_among(
+        &mut self,
+        source: impl Into<gix_hash::ObjectId>,
+        candidates: &[gix_hash::ObjectId],
+    ) -> Result<gix_hashtable::HashSet<gix_hash::ObjectId>, lookup_parents::Error> {
+        let mut remaining: gix_hashtable::HashSet<_> = candidates.iter().copied().collect();
+        let mut found = gix_hashtable::HashSet::default();
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut queue: VecDeque<_> = Some(source.into()).into_iter().filter(|id| seen.insert(*id)).collect();
+        while let Some(id) = queue.pop_front() {
+            if remaining.is_empty() {
+                break;
+            }
+            let commit = match try_lookup(&id, &*self.find, self.cache.as_ref(), &mut self.buf)? {
+                Some(commit) => commit,
+                None => continue,
+            };
+            if remaining.remove(&id) {
+                found.insert(id);
+            }
+            for parent_id in commit.iter_parents() {
+                let parent_id = parent_id?;
+                if seen.insert(parent_id) {
+                    queue.push_back(parent_id);
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    /// Return `true` if `ancestor` is reachable from `descendant`, similar to `git merge-base --is-ancestor <ancestor> <descendant>`.
+    /// Every commit is its own ancestor.
+    ///
+    /// If a commit-graph is available, its generation numbers are used to answer without traversal if `ancestor` is younger
+    /// than `descendant`, and to avoid traversing commits older than `ancestor` otherwise.
+    /// Commits that don't exist are skipped, and the graph itself remains unchanged.
+    pub fn is_ancestor(
+        &mut self,
+        ancestor: &gix_hash::oid,
+        descendant: &gix_hash::oid,
+    ) -> Result<bool, lookup_parents::Error> {
+        if ancestor == descendant {
+            return Ok(true);
+        }
+        let min_generation = match try_lookup(ancestor, &*self.find, self.cache.as_ref(), &mut self.buf)? {
+            Some(commit) => commit.generation(),
+            None => return Ok(false),
+        };
+        let generation_too_low = |generation: Option<Generation>| matches!((generation, min_generation), (Some(generation), Some(min)) if generation <= min);
+
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut queue = vec![descendant.to_owned()];
+        seen.insert(descendant.to_owned());
+        while let Some(id) = queue.pop() {
+            let commit = match try_lookup(&id, &*self.find, self.cache.as_ref(), &mut self.buf)? {
+                Some(commit) => commit,
+                None => continue,
+            };
+            if generation_too_low(commit.generation()) {
+                continue;
+            }
+            for parent_id in commit.iter_parents() {
+                let parent_id = parent_id?;
+                if parent_id == ancestor {
+                    return Ok(true);
+                }
+                if seen.insert(parent_id) {
+                    queue.push(parent_id);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Estimate how many commits `b` is behind `a` by the difference of their generation numbers, without traversing anything,
+    /// or return `None` if there is no commit-graph or if one of the commits isn't in it.
+    ///
+    /// Note that this is only an approximation useful for display, as generation numbers are the length of the longest
+    /// path to a root commit. It's exact for linear history, but merges make commits of all merged branches share generations,
+    /// and `b` might not even be an ancestor of `a`. If `b` has a higher generation than `a`, 0 is returned.
+    pub fn approx_behind(&self, a: &gix_hash::oid, b: &gix_hash::oid) -> Option<Generation> {
+        Some(self.generation(a)?.saturating_sub(self.generation(b)?))
+    }
+
+    /// Return the generation number of the commit named `id`, or `None` if there is no commit-graph or if `id` isn't in it.
+    ///
+    /// Generation numbers are never computed, which is why this is only available with a commit-graph.
+    pub fn generation(&self, id: &gix_hash::oid) -> Option<Generation> {
+        let cache = self.cache.as_ref()?;
+        cache.lookup(id).map(|pos| cache.commit_at(pos).generation())
+    }
+
+    /// Return the committer time of the commit named `id`, from the commit-graph if it contains the commit,
+    /// or by decoding the commit from the object database otherwise.
+    pub fn commit_time(&mut self, id: &gix_hash::oid) -> Result<SecondsSinceUnixEpoch, commit_time::Error> {
+        Ok(self.lookup(id)?.committer_timestamp()?)
+    }
+
     /// Turn ourselves into the underlying graph structure, which is a mere mapping between object ids and their data.
     pub fn detach(self) -> IdMap<T> {
         self.map
@@ -160,6 +438,7 @@ impl<'find, T> Graph<'find, T> {
             find: Box::new(objects),
             cache: cache.into(),
             map: gix_hashtable::HashMap::default(),
+            shallow_boundary: Default::default(),
             buf: Vec::new(),
             parent_buf: Vec::new(),
         }
@@ -196,6 +475,22 @@ impl<'find, T> Graph<'find, Commit<T>> {
         };
         Ok(self.map.get_mut(&id))
     }
+
+    /// Return the ids of all parents of commits in the graph that aren't in the graph themselves, in no particular order.
+    ///
+    /// After a walk that was stopped early, for instance after a certain amount of commits or at a certain date,
+    /// these are the commits just past the limit that were referenced as parents but never visited,
+    /// similar to the boundary of a shallow clone. They can be used to write a `shallow` file, or to continue the walk later.
+    ///
+    /// Only the parents stored with each commit are used, so nothing is looked up. This also means that parents that don't
+    /// exist in the object database, like the ones beyond the boundary of a shallow clone, are returned as well.
+    pub fn unexplored_parents(&self) -> impl Iterator<Item = gix_hash::ObjectId> + '_ {
+        let mut seen = gix_hashtable::HashSet::default();
+        self.map
+            .values()
+            .flat_map(|commit| commit.parents.iter().copied())
+            .filter(move |parent_id| !self.map.contains_key(parent_id) && seen.insert(*parent_id))
+    }
 }
 
 /// commit access
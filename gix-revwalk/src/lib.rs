@@ -25,6 +25,13 @@
 ///
 /// Additionally, and only if `T` is [`Commit<T>`][graph::Commit], there is *no need for an object cache* as we keep track of
 /// everything related to commit traversal in our own hashmap.
+///
+/// ### Shallow clones
+///
+/// In shallow clones, the parents of the commits at the boundary of the clone are recorded in these commits, but don't exist
+/// in the object database. Traversals never fail due to such missing parents, but treat them as if they didn't exist.
+/// Commits whose parents were found to be missing by [`insert_parents()`][Graph::insert_parents()] are remembered
+/// and can be obtained with [`shallow_boundary()`][Graph::shallow_boundary()].
 pub struct Graph<'find, T> {
     /// A way to resolve a commit from the object database.
     find: Box<dyn gix_object::Find + 'find>,
@@ -32,6 +39,8 @@ pub struct Graph<'find, T> {
     cache: Option<gix_commitgraph::Graph>,
     /// The set of cached commits that we have seen once, along with data associated with them.
     map: graph::IdMap<T>,
+    /// The commits whose parents were found to be missing, typically because they are at the boundary of a shallow clone.
+    shallow_boundary: gix_hashtable::HashSet<gix_hash::ObjectId>,
     /// A buffer for writing commit data into.
     buf: Vec<u8>,
     /// Another buffer we typically use to store parents.
@@ -48,4 +57,6 @@ pub mod graph;
 /// and as it stands our implementation is about 6% slower in practice, probably also depending on the size of the stored data.
 #[derive(Default)]
 pub struct PriorityQueue<K: Ord, T>(std::collections::BinaryHeap<queue::Item<K, T>>);
-mod queue;
+///
+#[allow(clippy::empty_docs)]
+pub mod queue;
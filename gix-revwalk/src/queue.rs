@@ -1,4 +1,7 @@
-use std::{cmp::Ordering, collections::BinaryHeap};
+use std::{
+    cmp::Ordering,
+    collections::{binary_heap, BinaryHeap},
+};
 
 use crate::PriorityQueue;
 
@@ -67,11 +70,34 @@ impl<K: Ord, T> PriorityQueue<K, T> {
     pub fn new() -> Self {
         PriorityQueue(Default::default())
     }
+
+    /// Create a new instance with enough capacity to hold at least `capacity` items without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PriorityQueue(BinaryHeap::with_capacity(capacity))
+    }
+
+    /// Reserve capacity for at least `additional` more items.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    /// Return the amount of items the queue can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
     /// Insert `value` so that it is ordered according to `key`.
     pub fn insert(&mut self, key: K, value: T) {
         self.0.push(Item { key, value });
     }
 
+    /// Move all items of `other` into this queue, leaving `other` empty.
+    ///
+    /// The queue is rebuilt at once if that is faster than inserting the items one by one.
+    pub fn append(&mut self, other: &mut Self) {
+        self.0.append(&mut other.0)
+    }
+
     /// Pop the highest-priority item value off the queue.
     pub fn pop_value(&mut self) -> Option<T> {
         self.0.pop().map(|t| t.value)
@@ -82,22 +108,54 @@ impl<K: Ord, T> PriorityQueue<K, T> {
         self.0.pop().map(|t| (t.key, t.value))
     }
 
-    /// Iterate all items ordered from highest to lowest priority.
+    /// Pop all items whose key is equal to the key of the highest-priority item off the queue, in arbitrary order.
+    ///
+    /// This is useful to process all items with the same priority together, like commits with the same commit time.
+    /// If the queue is empty, the returned vector is empty as well.
+    pub fn drain_equal_to_top(&mut self) -> Vec<(K, T)> {
+        let mut out = Vec::new();
+        while let Some(item) = self.0.peek_mut() {
+            if matches!(out.first(), Some((key, _)) if *key != item.key) {
+                break;
+            }
+            let item = binary_heap::PeekMut::pop(item);
+            out.push((item.key, item.value));
+        }
+        out
+    }
+
+    /// Iterate all item values in arbitrary order.
     pub fn iter_unordered(&self) -> impl Iterator<Item = &T> {
         self.0.iter().map(|t| &t.value)
     }
 
+    /// Iterate all item keys and values in arbitrary order, which is *not* the order in which they would be popped.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &T)> {
+        self.0.iter().map(|t| (&t.key, &t.value))
+    }
+
     /// Turn this instance into an iterator over its keys and values in arbitrary order.
     pub fn into_iter_unordered(self) -> impl Iterator<Item = (K, T)> {
         self.0.into_vec().into_iter().map(|item| (item.key, item.value))
     }
 
+    /// Turn this instance into a vector of its keys and values in arbitrary order.
+    pub fn into_vec(self) -> Vec<(K, T)> {
+        self.into_iter_unordered().collect()
+    }
+
+    /// Turn this instance into an iterator over its keys and values from highest to lowest priority,
+    /// just like calling [`pop()`](Self::pop()) repeatedly would.
+    pub fn into_iter_sorted(self) -> IntoIterSorted<K, T> {
+        IntoIterSorted(self)
+    }
+
     /// Return true if the queue is empty.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
 
-    /// Return true the amount of items on the queue.
+    /// Return the amount of items on the queue.
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -107,18 +165,83 @@ impl<K: Ord, T> PriorityQueue<K, T> {
         self.0.peek().map(|e| (&e.key, &e.value))
     }
 
+    /// Returns a guard for mutating the greatest item as ordered by `K`, if the queue is not empty, without removing it.
+    ///
+    /// If the key is changed through the guard, the item is moved to its new position in the queue once the guard is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, K, T>> {
+        self.0.peek_mut().map(PeekMut)
+    }
+
     /// Drop all items from the queue, without changing its capacity.
     pub fn clear(&mut self) {
         self.0.clear()
     }
 }
 
+/// A guard to mutate the greatest item of a [`PriorityQueue`], created by [`PriorityQueue::peek_mut()`].
+pub struct PeekMut<'queue, K: Ord, T>(binary_heap::PeekMut<'queue, Item<K, T>>);
+
+impl<'queue, K: Ord, T> PeekMut<'queue, K, T> {
+    /// Return the key of the item.
+    pub fn key(&self) -> &K {
+        &self.0.key
+    }
+
+    /// Return the value of the item.
+    pub fn value(&self) -> &T {
+        &self.0.value
+    }
+
+    /// Return the key of the item for mutation, which re-sorts it into the queue when the guard is dropped.
+    pub fn key_mut(&mut self) -> &mut K {
+        &mut self.0.key
+    }
+
+    /// Return the value of the item for mutation.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.0.value
+    }
+
+    /// Remove the item from the queue and return its key and value.
+    pub fn pop(this: Self) -> (K, T) {
+        let item = binary_heap::PeekMut::pop(this.0);
+        (item.key, item.value)
+    }
+}
+
+/// An iterator over the keys and values of a [`PriorityQueue`] from highest to lowest priority,
+/// created by [`PriorityQueue::into_iter_sorted()`].
+pub struct IntoIterSorted<K: Ord, T>(PriorityQueue<K, T>);
+
+impl<K: Ord, T> Iterator for IntoIterSorted<K, T> {
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<K: Ord, T> ExactSizeIterator for IntoIterSorted<K, T> {}
+
 impl<K: Ord, T> FromIterator<(K, T)> for PriorityQueue<K, T> {
+    /// Build the queue from all items at once, which is faster than inserting them one by one.
     fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Self {
-        let mut q = PriorityQueue(BinaryHeap::new());
-        for (k, v) in iter {
-            q.insert(k, v);
-        }
-        q
+        PriorityQueue(
+            iter.into_iter()
+                .map(|(key, value)| Item { key, value })
+                .collect::<Vec<_>>()
+                .into(),
+        )
+    }
+}
+
+impl<K: Ord, T> Extend<(K, T)> for PriorityQueue<K, T> {
+    /// Insert all items, rebuilding the queue at once if that is faster than inserting them one by one.
+    fn extend<I: IntoIterator<Item = (K, T)>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|(key, value)| Item { key, value }))
     }
 }
@@ -0,0 +1,40 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function commit_at() {
+  GIT_COMMITTER_DATE="$2 +0200" GIT_AUTHOR_DATE="$2 +0200" git commit -q --allow-empty -m "$1"
+  git tag "$1"
+}
+
+function merge_at() {
+  GIT_COMMITTER_DATE="$3 +0200" GIT_AUTHOR_DATE="$3 +0200" git merge -q "$2" -m "$1"
+  git tag "$1"
+}
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+commit_at root "2000-01-01 00:00:00"
+commit_at base "2000-01-02 00:00:00"
+
+git checkout -q -b a
+commit_at a1 "2000-01-03 00:00:00"
+git checkout -q -b b base
+commit_at b1 "2000-01-04 00:00:00"
+
+git checkout -q a
+merge_at ma b1 "2000-01-05 00:00:00"
+git checkout -q b
+merge_at mb a1 "2000-01-06 00:00:00"
+
+git checkout -q a
+commit_at a2 "2000-01-07 00:00:00"
+git checkout -q b
+commit_at b2 "2000-01-08 00:00:00"
+
+git checkout -q --orphan lonely
+commit_at unrelated "2000-01-09 00:00:00"
+
+git commit-graph write --no-progress --reachable
+git repack -adq
@@ -0,0 +1,16 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+git checkout -q -b main
+git commit -q --allow-empty -m root
+
+for branch in $(seq 100); do
+  git checkout -q -b "branch$branch" main
+  git commit -q --allow-empty -m "$branch-1"
+  git commit -q --allow-empty -m "$branch-2"
+done
+
+git commit-graph write --no-progress --reachable
+git repack -adq
@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+git commit -q --allow-empty -m c1
+git tag root
+git commit -q --allow-empty -m c2
+
+git checkout -q -b branch1
+git commit -q --allow-empty -m b1c1
+git checkout -q -b branch2 main
+git commit -q --allow-empty -m b2c1
+git checkout -q -b branch3 main
+git commit -q --allow-empty -m b3c1
+
+git checkout -q main
+git commit -q --allow-empty -m c3
+git tag linear
+git merge -q branch1 -m merge
+git tag merge
+git merge -q branch2 branch3 -m octopus
+git tag octopus
+
+git commit-graph write --no-progress --reachable
+git repack -adq
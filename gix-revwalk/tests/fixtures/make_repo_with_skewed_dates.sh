@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function commit_at() {
+  GIT_COMMITTER_DATE="$2 +0200" GIT_AUTHOR_DATE="$2 +0200" git commit -q --allow-empty -m "$1"
+  git tag "$1"
+}
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+commit_at root "2000-01-05 00:00:00"
+commit_at skewed "2000-01-01 00:00:00"
+git checkout -q -b side
+commit_at side "2000-01-03 00:00:00"
+git checkout -q main
+commit_at tip "2000-01-10 00:00:00"
+GIT_COMMITTER_DATE="2000-01-11 00:00:00 +0200" git merge -q side -m merge
+git tag merge
+
+git checkout -q -b other root
+commit_at other "2000-01-06 00:00:00"
+
+git checkout -q --orphan unrelated
+commit_at unrelated-root "2000-02-26 00:00:00"
+commit_at unrelated-skewed "2000-02-25 00:00:00"
+commit_at unrelated-base "2000-02-24 00:00:00"
+commit_at unrelated-local "2000-02-28 00:00:00"
+git checkout -q -b unrelated-side unrelated-base
+commit_at unrelated-old "2000-02-01 00:00:00"
+commit_at unrelated-upstream "2000-02-20 00:00:00"
+
+git commit-graph write --no-progress --reachable
+git repack -adq
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+git checkout -q -b main
+git commit -q --allow-empty -m c1
+git commit -q --allow-empty -m c2
+git commit -q --allow-empty -m c3
+
+git checkout -q -b other main~1
+git commit -q --allow-empty -m unreachable
+git checkout -q main
+git branch -D other
+
+git commit-graph write --no-progress --reachable
+git repack -adq
@@ -0,0 +1,731 @@
+use crate::hex_to_id;
+
+mod commit {
+    #[test]
+    fn size_of_commit() {
+        assert_eq!(
+            std::mem::size_of::<gix_revwalk::graph::Commit<()>>(),
+            48,
+            "We might see quite a lot of these, so they shouldn't grow unexpectedly"
+        )
+    }
+}
+
+fn odb() -> gix_odb::Handle {
+    odb_of("make_repo_with_merges.sh")
+}
+
+fn commit_graph() -> gix_commitgraph::Graph {
+    commit_graph_of("make_repo_with_merges.sh")
+}
+
+fn odb_of(script: &str) -> gix_odb::Handle {
+    gix_odb::at(fixture_path(script).join(".git/objects")).unwrap()
+}
+
+fn commit_graph_of(script: &str) -> gix_commitgraph::Graph {
+    gix_commitgraph::Graph::from_info_dir(&fixture_path(script).join(".git/objects/info")).unwrap()
+}
+
+fn fixture_path(script: &str) -> std::path::PathBuf {
+    gix_testtools::scripted_fixture_read_only(script).unwrap()
+}
+
+#[test]
+fn parent_count() -> crate::Result {
+    let store = odb();
+    for cache in [None, Some(commit_graph())] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        for (id, expected) in [
+            ("134385f6d781b7e97062102c6a483440bfda2a03", 0), // root
+            ("17d78c64cef6c33a10a604573fd2c429e477fd63", 1), // linear
+            ("c36aaefbeedf825ead8a3090da1b1ecf096ec90c", 2), // merge
+            ("342808810647ac574d804186b84baf70993bbb27", 3), // octopus
+        ] {
+            assert_eq!(graph.parent_count(&hex_to_id(id))?, expected, "{id}");
+        }
+        assert!(
+            graph.parent_count(&gix_hash::Kind::Sha1.null()).is_err(),
+            "non-existing commits are an error"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn earliest_commit() -> crate::Result {
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let merge = hex_to_id("eabd15dcbe8b33df68f98a63afaff4f9822c5aae");
+    let tip = hex_to_id("d8557d4c8da85e81e1f5ea78551cab512f8fef92");
+    let skewed = hex_to_id("f8a1c6cd38e553435bf30c3ebbeead37356869f5");
+    for cache in [None, Some(commit_graph_of("make_repo_with_skewed_dates.sh"))] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        let (id, time) = graph.earliest_commit(Some(merge), None)?.expect("commits exist");
+        assert_eq!(
+            id, skewed,
+            "the root commit is younger than its child due to clock skew"
+        );
+        assert_eq!(time.seconds, 946677600);
+        assert_eq!(
+            time.offset, 7200,
+            "the time-zone is retained even if a commit-graph is used"
+        );
+
+        assert_eq!(
+            graph.earliest_commit(Some(merge), Some(2))?.map(|(id, _)| id),
+            Some(tip),
+            "the limit bounds the traversal, so only the merge and its first parent are seen"
+        );
+        assert_eq!(
+            graph
+                .earliest_commit([gix_hash::Kind::Sha1.null(), tip], None)?
+                .map(|(id, _)| id),
+            Some(skewed),
+            "tips that don't exist are ignored"
+        );
+        assert_eq!(graph.earliest_commit(Some(gix_hash::Kind::Sha1.null()), None)?, None);
+    }
+    Ok(())
+}
+
+#[test]
+fn reachable_from() -> crate::Result {
+    let store = odb_of("make_repo_with_unreachable_commit.sh");
+    let c1 = hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03");
+    let c2 = hex_to_id("9902e3c3e8f0c569b4ab295ddf473e6de763e1e7");
+    let c3 = hex_to_id("17d78c64cef6c33a10a604573fd2c429e477fd63");
+    let unreachable = hex_to_id("6076a5dec95cecd2e033a4147cfafa492a1b9564");
+    for cache in [None, Some(commit_graph_of("make_repo_with_unreachable_commit.sh"))] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        let reachable = graph.reachable_from(Some(c3), &[])?;
+        assert_eq!(reachable.len(), 3);
+        for id in [c1, c2, c3] {
+            assert!(reachable.contains(&id), "{id}");
+        }
+        assert!(
+            !reachable.contains(&unreachable),
+            "the commit exists, but no tip leads to it"
+        );
+
+        let reachable = graph.reachable_from([c3, unreachable, gix_hash::Kind::Sha1.null()], &[])?;
+        assert_eq!(
+            reachable.len(),
+            4,
+            "tips are included in the closure, non-existing ones are ignored"
+        );
+
+        let reachable = graph.reachable_from(Some(c3), &[c2])?;
+        assert_eq!(reachable.len(), 2, "parents of shallow commits aren't followed");
+        assert!(reachable.contains(&c2) && !reachable.contains(&c1));
+    }
+    Ok(())
+}
+
+#[test]
+fn ancestors_among() -> crate::Result {
+    struct CountingFind<'a> {
+        inner: &'a gix_odb::Handle,
+        lookups: &'a std::cell::Cell<usize>,
+    }
+
+    impl gix_object::Find for CountingFind<'_> {
+        fn try_find<'a>(
+            &self,
+            id: &gix_hash::oid,
+            buffer: &'a mut Vec<u8>,
+        ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
+            self.lookups.set(self.lookups.get() + 1);
+            self.inner.try_find(id, buffer)
+        }
+    }
+
+    let store = odb_of("make_repo_with_unreachable_commit.sh");
+    let c1 = hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03");
+    let c2 = hex_to_id("9902e3c3e8f0c569b4ab295ddf473e6de763e1e7");
+    let c3 = hex_to_id("17d78c64cef6c33a10a604573fd2c429e477fd63");
+    let unreachable = hex_to_id("6076a5dec95cecd2e033a4147cfafa492a1b9564");
+    let lookups = std::cell::Cell::new(0);
+    let mut graph = gix_revwalk::Graph::<()>::new(
+        CountingFind {
+            inner: &store,
+            lookups: &lookups,
+        },
+        None,
+    );
+
+    let ancestors = graph.ancestors_among(c3, &[c1, c2, unreachable, gix_hash::Kind::Sha1.null()])?;
+    assert_eq!(ancestors.len(), 2);
+    assert!(ancestors.contains(&c1) && ancestors.contains(&c2));
+    assert_eq!(
+        lookups.get(),
+        3,
+        "each of the reachable commits is looked up once in a single walk, even though not all candidates are found"
+    );
+
+    lookups.set(0);
+    let ancestors = graph.ancestors_among(c3, &[c3, c2])?;
+    assert_eq!(ancestors.len(), 2, "the source itself is its own ancestor");
+    assert_eq!(lookups.get(), 2, "the walk stops once all candidates were found");
+
+    lookups.set(0);
+    assert!(
+        graph.ancestors_among(c1, &[c2, c3])?.is_empty(),
+        "descendants aren't ancestors"
+    );
+    assert!(graph.ancestors_among(c3, &[])?.is_empty());
+    assert_eq!(lookups.get(), 1, "nothing is looked up if there is nothing to find");
+    Ok(())
+}
+
+#[test]
+fn shallow_boundary() -> crate::Result {
+    struct ShallowFind<'a> {
+        inner: &'a gix_odb::Handle,
+        missing: gix_hash::ObjectId,
+    }
+
+    impl gix_object::Find for ShallowFind<'_> {
+        fn try_find<'a>(
+            &self,
+            id: &gix_hash::oid,
+            buffer: &'a mut Vec<u8>,
+        ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
+            if id == self.missing {
+                return Ok(None);
+            }
+            self.inner.try_find(id, buffer)
+        }
+    }
+
+    let store = odb_of("make_repo_with_unreachable_commit.sh");
+    let c1 = hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03");
+    let c2 = hex_to_id("9902e3c3e8f0c569b4ab295ddf473e6de763e1e7");
+    let c3 = hex_to_id("17d78c64cef6c33a10a604573fd2c429e477fd63");
+    let mut graph = gix_revwalk::Graph::<()>::new(
+        ShallowFind {
+            inner: &store,
+            missing: c1,
+        },
+        None,
+    );
+
+    graph.insert_parents(&c3, &mut |_, _| (), &mut |_, _| {}, false)?;
+    assert!(graph.shallow_boundary().is_empty(), "the parent of c3 exists");
+
+    graph.insert_parents(&c2, &mut |_, _| (), &mut |_, _| {}, false)?;
+    assert!(!graph.contains(&c1), "missing parents aren't inserted");
+    assert_eq!(
+        graph.shallow_boundary().iter().collect::<Vec<_>>(),
+        [&c2],
+        "…but the commit that refers to them is a boundary commit"
+    );
+
+    graph.clear();
+    assert!(
+        graph.shallow_boundary().is_empty(),
+        "clearing the graph resets the boundary"
+    );
+    Ok(())
+}
+
+#[test]
+fn approx_behind() -> crate::Result {
+    let store = odb_of("make_repo_with_unreachable_commit.sh");
+    let c1 = hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03");
+    let c3 = hex_to_id("17d78c64cef6c33a10a604573fd2c429e477fd63");
+
+    let graph = gix_revwalk::Graph::<()>::new(&store, None);
+    assert_eq!(
+        graph.approx_behind(&c3, &c1),
+        None,
+        "without commit-graph there is no estimate"
+    );
+
+    let mut graph = gix_revwalk::Graph::<()>::new(&store, commit_graph_of("make_repo_with_unreachable_commit.sh"));
+    let exact = graph.reachable_from(Some(c3), &[])?.len() - graph.reachable_from(Some(c1), &[])?.len();
+    assert_eq!(
+        graph.approx_behind(&c3, &c1),
+        Some(exact as u32),
+        "in linear history, generation numbers are exact"
+    );
+    assert_eq!(graph.approx_behind(&c1, &c3), Some(0), "being ahead isn't negative");
+    assert_eq!(
+        graph.approx_behind(&c3, &hex_to_id("6076a5dec95cecd2e033a4147cfafa492a1b9564")),
+        None,
+        "unreachable commits aren't in the commit-graph"
+    );
+    Ok(())
+}
+
+#[test]
+fn unexplored_parents() -> crate::Result {
+    let store = odb();
+    let octopus = hex_to_id("342808810647ac574d804186b84baf70993bbb27");
+    let merge = hex_to_id("c36aaefbeedf825ead8a3090da1b1ecf096ec90c");
+    let linear = hex_to_id("17d78c64cef6c33a10a604573fd2c429e477fd63");
+    let b1c1 = hex_to_id("578a2f3e1a6b9346ec68fde1aedf3bd45811dc8c");
+    let b2c1 = hex_to_id("05d3d63c6863d7552502c926553449ffa98c2664");
+    let b3c1 = hex_to_id("dfcd79c111fd4493e6e42c9f5ccc8847a5beb3f6");
+    let sorted = |mut ids: Vec<gix_hash::ObjectId>| {
+        ids.sort();
+        ids
+    };
+    for cache in [None, Some(commit_graph())] {
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(&store, cache);
+        let visit = |graph: &mut gix_revwalk::Graph<'_, gix_revwalk::graph::Commit<()>>, id: gix_hash::ObjectId| {
+            graph.try_lookup_or_insert_commit(id, |_| {}).map(|_| ())
+        };
+        assert_eq!(graph.unexplored_parents().count(), 0, "nothing was seen yet");
+
+        visit(&mut graph, octopus)?;
+        assert_eq!(
+            sorted(graph.unexplored_parents().collect()),
+            sorted(vec![merge, b2c1, b3c1]),
+            "all parents are just past the limit"
+        );
+
+        visit(&mut graph, merge)?;
+        assert_eq!(
+            sorted(graph.unexplored_parents().collect()),
+            sorted(vec![linear, b1c1, b2c1, b3c1])
+        );
+
+        loop {
+            let unexplored: Vec<_> = graph.unexplored_parents().collect();
+            if unexplored.is_empty() {
+                break;
+            }
+            for id in unexplored {
+                visit(&mut graph, id)?;
+            }
+        }
+        assert_eq!(
+            graph.detach().len(),
+            8,
+            "all commits were visited, the root has no parents to explore"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn walk_tips_time_ordered() -> crate::Result {
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let tip = hex_to_id("d8557d4c8da85e81e1f5ea78551cab512f8fef92");
+    let side = hex_to_id("4dec615aca9db1734819b3b6f4df02647323b3be");
+    let skewed = hex_to_id("f8a1c6cd38e553435bf30c3ebbeead37356869f5");
+    let root = hex_to_id("688f878176a1b31aa258a585b8f5feb5a3c07d38");
+    for cache in [None, Some(commit_graph_of("make_repo_with_skewed_dates.sh"))] {
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(&store, cache);
+        let ids = graph
+            .walk_tips_time_ordered([side, tip, gix_hash::Kind::Sha1.null()])?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            ids,
+            [tip, side, skewed, root],
+            "shared ancestors are returned once, youngest first across both tips, and missing tips are ignored"
+        );
+
+        assert_eq!(
+            graph
+                .walk_tips_time_ordered([tip, tip])?
+                .collect::<Result<Vec<_>, _>>()?,
+            [tip, skewed, root]
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn log_order_walk() -> crate::Result {
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let tip = hex_to_id("d8557d4c8da85e81e1f5ea78551cab512f8fef92");
+    let other = hex_to_id("f6e0ddd5249b3641a126b0791e46e9deb75ae4af");
+    let skewed = hex_to_id("f8a1c6cd38e553435bf30c3ebbeead37356869f5");
+    let root = hex_to_id("688f878176a1b31aa258a585b8f5feb5a3c07d38");
+    let merge = hex_to_id("eabd15dcbe8b33df68f98a63afaff4f9822c5aae");
+    let side = hex_to_id("4dec615aca9db1734819b3b6f4df02647323b3be");
+    for cache in [None, Some(commit_graph_of("make_repo_with_skewed_dates.sh"))] {
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(&store, cache);
+        assert_eq!(
+            graph
+                .walk_tips_time_ordered([tip, other])?
+                .collect::<Result<Vec<_>, _>>()?,
+            [tip, other, root, skewed],
+            "by time alone, `root` is returned before its child `skewed` as it's younger"
+        );
+        assert_eq!(
+            graph.log_order_walk([tip, other, gix_hash::Kind::Sha1.null()])?,
+            [tip, other, skewed, root],
+            "children are always returned before their parents, and missing tips are ignored"
+        );
+        assert_eq!(
+            graph.log_order_walk([merge, tip])?,
+            [merge, tip, side, skewed, root],
+            "tips that are reachable from other tips wait for their children"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn merge_base() -> crate::Result {
+    let store = odb_of("make_repo_with_criss_cross_merges.sh");
+    let base = hex_to_id("9dfd14ffeadd746b24e35557a30de0be6923ed0d");
+    let a1 = hex_to_id("85fc45026a0c03eba0b8bc1897daf94d2cf558ec");
+    let b1 = hex_to_id("3d6769a762a584d3bffcaa2f7621f6d5a9ea91de");
+    let ma = hex_to_id("67c9546b011d13fbe3c1052642a5a9eaf221e761");
+    let mb = hex_to_id("ce1f40b5d5fa9d6ff3141c216c713064aafe2425");
+    let a2 = hex_to_id("7c8cff6ebe41dba5d70898b9021b334dcecb8d30");
+    let b2 = hex_to_id("a3f6dbb2b93c1895aaea749cc4fafbf045a8e30d");
+    let unrelated = hex_to_id("c37ab2bc2c98f22e436fa97d065e6e2a59a8b83e");
+    for cache in [None, Some(commit_graph_of("make_repo_with_criss_cross_merges.sh"))] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        assert_eq!(
+            graph.merge_bases(&[a2, b2])?,
+            [b1, a1],
+            "criss-cross merges have two merge bases, youngest first"
+        );
+        assert_eq!(graph.merge_bases(&[ma, mb])?, [b1, a1]);
+        assert_eq!(
+            graph.merge_base(&a2, &b2)?,
+            Some(b1),
+            "the youngest merge base is the best one, just like in `git merge-base`"
+        );
+        assert_eq!(
+            graph.merge_base(&a1, &ma)?,
+            Some(a1),
+            "an ancestor is its own merge base"
+        );
+        assert_eq!(graph.merge_base(&a2, &a2)?, Some(a2));
+        assert_eq!(
+            graph.merge_bases(&[a2, b2, base])?,
+            [base],
+            "octopus merge bases are common to all commits"
+        );
+        assert_eq!(graph.merge_bases(&[ma, mb, a2])?, [b1, a1]);
+        assert_eq!(graph.merge_bases(&[a2])?, [a2]);
+        assert!(graph.merge_bases(&[])?.is_empty());
+
+        assert_eq!(graph.merge_base(&a2, &unrelated)?, None, "no common history");
+        assert!(graph.merge_bases(&[a2, b2, unrelated])?.is_empty());
+        assert_eq!(
+            graph.merge_base(&a2, &gix_hash::Kind::Sha1.null())?,
+            None,
+            "missing commits are ignored"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn is_ancestor() -> crate::Result {
+    let store = odb_of("make_repo_with_criss_cross_merges.sh");
+    let root = hex_to_id("162bbce1a0f17e1dac50a28b5f2704c603a72d40");
+    let base = hex_to_id("9dfd14ffeadd746b24e35557a30de0be6923ed0d");
+    let a1 = hex_to_id("85fc45026a0c03eba0b8bc1897daf94d2cf558ec");
+    let b1 = hex_to_id("3d6769a762a584d3bffcaa2f7621f6d5a9ea91de");
+    let mb = hex_to_id("ce1f40b5d5fa9d6ff3141c216c713064aafe2425");
+    let a2 = hex_to_id("7c8cff6ebe41dba5d70898b9021b334dcecb8d30");
+    let b2 = hex_to_id("a3f6dbb2b93c1895aaea749cc4fafbf045a8e30d");
+    let unrelated = hex_to_id("c37ab2bc2c98f22e436fa97d065e6e2a59a8b83e");
+    for cache in [None, Some(commit_graph_of("make_repo_with_criss_cross_merges.sh"))] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        assert!(graph.is_ancestor(&a2, &a2)?, "every commit is its own ancestor");
+        assert!(graph.is_ancestor(&root, &base)?, "linear history");
+        assert!(graph.is_ancestor(&root, &b2)?);
+        assert!(graph.is_ancestor(&a1, &mb)?, "merged history");
+        assert!(graph.is_ancestor(&a1, &b2)?);
+
+        assert!(!graph.is_ancestor(&base, &root)?, "descendants aren't ancestors");
+        assert!(!graph.is_ancestor(&b2, &root)?);
+        assert!(!graph.is_ancestor(&a1, &b1)?, "siblings aren't ancestors of each other");
+        assert!(!graph.is_ancestor(&a2, &b2)?);
+        assert!(!graph.is_ancestor(&root, &unrelated)?, "there is no common history");
+        assert!(
+            !graph.is_ancestor(&gix_hash::Kind::Sha1.null(), &b2)?,
+            "missing commits are never ancestors"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn ancestors() -> crate::Result {
+    use gix_revwalk::graph::ancestors::Sorting;
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let tip = hex_to_id("d8557d4c8da85e81e1f5ea78551cab512f8fef92");
+    let other = hex_to_id("f6e0ddd5249b3641a126b0791e46e9deb75ae4af");
+    let skewed = hex_to_id("f8a1c6cd38e553435bf30c3ebbeead37356869f5");
+    let root = hex_to_id("688f878176a1b31aa258a585b8f5feb5a3c07d38");
+    let merge = hex_to_id("eabd15dcbe8b33df68f98a63afaff4f9822c5aae");
+    let side = hex_to_id("4dec615aca9db1734819b3b6f4df02647323b3be");
+    for cache in [None, Some(commit_graph_of("make_repo_with_skewed_dates.sh"))] {
+        let has_generations = cache.is_some();
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(&store, cache);
+        assert_eq!(
+            graph
+                .ancestors([tip, other, gix_hash::Kind::Sha1.null()], Sorting::ByCommitTime, None)?
+                .collect::<Result<Vec<_>, _>>()?,
+            [tip, other, root, skewed],
+            "by time alone, `root` is returned before its child `skewed` as it's younger, and missing tips are ignored"
+        );
+        assert_eq!(
+            graph
+                .ancestors([tip, other], Sorting::DateOrder, None)?
+                .collect::<Result<Vec<_>, _>>()?,
+            [tip, other, skewed, root],
+            "children are always returned before their parents"
+        );
+        assert_eq!(
+            graph
+                .ancestors([merge, tip], Sorting::DateOrder, None)?
+                .collect::<Result<Vec<_>, _>>()?,
+            [merge, tip, side, skewed, root]
+        );
+        assert_eq!(
+            graph
+                .ancestors([merge, tip], Sorting::TopoOrder, None)?
+                .collect::<Result<Vec<_>, _>>()?,
+            [merge, side, tip, skewed, root],
+            "lines of history aren't interleaved"
+        );
+        assert_eq!(graph.detach().len(), 6, "all visited commits are kept in the graph");
+
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(
+            &store,
+            has_generations.then(|| commit_graph_of("make_repo_with_skewed_dates.sh")),
+        );
+        let mut iter = graph.ancestors(Some(merge), Sorting::ByCommitTime, Some(3))?;
+        let mut ids = Vec::new();
+        while let Some(id) = iter.next().transpose()? {
+            assert!(iter.get(&id).is_some(), "data of returned commits is available");
+            ids.push(id);
+        }
+        if has_generations {
+            assert_eq!(
+                ids,
+                [merge, tip, side],
+                "commits below the minimal generation are cut off"
+            );
+        } else {
+            assert_eq!(
+                ids,
+                [merge, tip, side, skewed, root],
+                "without generation numbers, nothing is cut off"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn generation_and_commit_time() -> crate::Result {
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let root = hex_to_id("688f878176a1b31aa258a585b8f5feb5a3c07d38");
+    let merge = hex_to_id("eabd15dcbe8b33df68f98a63afaff4f9822c5aae");
+
+    let mut graph = gix_revwalk::Graph::<()>::new(&store, None);
+    assert_eq!(
+        graph.generation(&root),
+        None,
+        "without commit-graph there are no generations"
+    );
+    assert_eq!(
+        graph.commit_time(&root)?,
+        947023200,
+        "the time is decoded from the object"
+    );
+
+    let mut graph = gix_revwalk::Graph::<()>::new(&store, commit_graph_of("make_repo_with_skewed_dates.sh"));
+    assert_eq!(graph.generation(&root), Some(1));
+    assert_eq!(graph.generation(&merge), Some(4));
+    assert_eq!(
+        graph.commit_time(&root)?,
+        947023200,
+        "the time is taken from the commit-graph"
+    );
+    assert!(
+        graph.commit_time(&gix_hash::Kind::Sha1.null()).is_err(),
+        "missing commits are an error"
+    );
+
+    let store = odb_of("make_repo_with_unreachable_commit.sh");
+    let unreachable = hex_to_id("6076a5dec95cecd2e033a4147cfafa492a1b9564");
+    let mut graph = gix_revwalk::Graph::<()>::new(&store, commit_graph_of("make_repo_with_unreachable_commit.sh"));
+    assert_eq!(graph.generation(&unreachable), None, "it's not in the commit-graph");
+    assert!(
+        graph.commit_time(&unreachable)? > 0,
+        "but its time can still be obtained from the object database"
+    );
+    Ok(())
+}
+
+#[test]
+fn topo_order() -> crate::Result {
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let tip = hex_to_id("d8557d4c8da85e81e1f5ea78551cab512f8fef92");
+    let other = hex_to_id("f6e0ddd5249b3641a126b0791e46e9deb75ae4af");
+    let skewed = hex_to_id("f8a1c6cd38e553435bf30c3ebbeead37356869f5");
+    let root = hex_to_id("688f878176a1b31aa258a585b8f5feb5a3c07d38");
+    let merge = hex_to_id("eabd15dcbe8b33df68f98a63afaff4f9822c5aae");
+    let side = hex_to_id("4dec615aca9db1734819b3b6f4df02647323b3be");
+    for cache in [None, Some(commit_graph_of("make_repo_with_skewed_dates.sh"))] {
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(&store, cache);
+        assert_eq!(
+            graph.topo_order([other, merge, tip, gix_hash::Kind::Sha1.null()])?,
+            [merge, side, tip, skewed, other, root],
+            "lines of history aren't interleaved, the last parent comes first, and missing tips are ignored"
+        );
+        assert_eq!(
+            graph.log_order_walk([other, merge])?,
+            [merge, tip, other, side, skewed, root],
+            "ordering by time interleaves lines of history"
+        );
+        assert_eq!(graph.topo_order([skewed])?, [skewed, root]);
+    }
+
+    let store = odb_of("make_repo_with_criss_cross_merges.sh");
+    let root = hex_to_id("162bbce1a0f17e1dac50a28b5f2704c603a72d40");
+    let base = hex_to_id("9dfd14ffeadd746b24e35557a30de0be6923ed0d");
+    let a1 = hex_to_id("85fc45026a0c03eba0b8bc1897daf94d2cf558ec");
+    let b1 = hex_to_id("3d6769a762a584d3bffcaa2f7621f6d5a9ea91de");
+    let ma = hex_to_id("67c9546b011d13fbe3c1052642a5a9eaf221e761");
+    let mb = hex_to_id("ce1f40b5d5fa9d6ff3141c216c713064aafe2425");
+    let a2 = hex_to_id("7c8cff6ebe41dba5d70898b9021b334dcecb8d30");
+    let b2 = hex_to_id("a3f6dbb2b93c1895aaea749cc4fafbf045a8e30d");
+    let unrelated = hex_to_id("c37ab2bc2c98f22e436fa97d065e6e2a59a8b83e");
+    for cache in [None, Some(commit_graph_of("make_repo_with_criss_cross_merges.sh"))] {
+        let mut graph = gix_revwalk::Graph::<gix_revwalk::graph::Commit<()>>::new(&store, cache);
+        assert_eq!(
+            graph.topo_order([a2, b2, unrelated])?,
+            [unrelated, b2, mb, a2, ma, b1, a1, base, root],
+            "multiple roots are supported, each tip starts its own line of history"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn count_ahead_behind_and_reachable() -> crate::Result {
+    let store = odb_of("make_repo_with_criss_cross_merges.sh");
+    let root = hex_to_id("162bbce1a0f17e1dac50a28b5f2704c603a72d40");
+    let base = hex_to_id("9dfd14ffeadd746b24e35557a30de0be6923ed0d");
+    let a1 = hex_to_id("85fc45026a0c03eba0b8bc1897daf94d2cf558ec");
+    let b1 = hex_to_id("3d6769a762a584d3bffcaa2f7621f6d5a9ea91de");
+    let ma = hex_to_id("67c9546b011d13fbe3c1052642a5a9eaf221e761");
+    let mb = hex_to_id("ce1f40b5d5fa9d6ff3141c216c713064aafe2425");
+    let a2 = hex_to_id("7c8cff6ebe41dba5d70898b9021b334dcecb8d30");
+    let b2 = hex_to_id("a3f6dbb2b93c1895aaea749cc4fafbf045a8e30d");
+    let unrelated = hex_to_id("c37ab2bc2c98f22e436fa97d065e6e2a59a8b83e");
+    for cache in [None, Some(commit_graph_of("make_repo_with_criss_cross_merges.sh"))] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        assert_eq!(
+            graph.count_ahead_behind(&a2, &b2)?,
+            (2, 2),
+            "the common history of criss-cross merges isn't counted"
+        );
+        assert_eq!(graph.count_ahead_behind(&ma, &mb)?, (1, 1));
+        assert_eq!(
+            graph.count_ahead_behind(&a1, &b2)?,
+            (0, 3),
+            "an ancestor is only behind"
+        );
+        assert_eq!(
+            graph.count_ahead_behind(&b2, &root)?,
+            (5, 0),
+            "a descendant is only ahead"
+        );
+        assert_eq!(graph.count_ahead_behind(&a2, &a2)?, (0, 0));
+        assert_eq!(
+            graph.count_ahead_behind(&a2, &unrelated)?,
+            (6, 1),
+            "without common history, everything is counted"
+        );
+
+        assert_eq!(graph.count_reachable([a2, b2], &[base])?, 6);
+        assert_eq!(graph.count_reachable(Some(a2), &[b1, a1])?, 2);
+        assert_eq!(graph.count_reachable(Some(a2), &[])?, 6, "everything is reachable");
+        assert_eq!(graph.count_reachable(Some(a1), &[b2])?, 0);
+    }
+
+    let store = odb_of("make_repo_with_skewed_dates.sh");
+    let other = hex_to_id("f6e0ddd5249b3641a126b0791e46e9deb75ae4af");
+    let skewed = hex_to_id("f8a1c6cd38e553435bf30c3ebbeead37356869f5");
+    let merge = hex_to_id("eabd15dcbe8b33df68f98a63afaff4f9822c5aae");
+    let unrelated_local = hex_to_id("1e29d257c51f3b1bbdff6f0c95a9594619010586");
+    let unrelated_upstream = hex_to_id("5c0f7b0d8510bf9042dd578ec6c3eb41afeeefc1");
+    for cache in [None, Some(commit_graph_of("make_repo_with_skewed_dates.sh"))] {
+        let mut graph = gix_revwalk::Graph::<()>::new(&store, cache);
+        assert_eq!(
+            graph.count_ahead_behind(&other, &merge)?,
+            (1, 4),
+            "clock skew doesn't affect the result"
+        );
+        assert_eq!(graph.count_ahead_behind(&skewed, &other)?, (1, 1));
+        assert_eq!(
+            graph.count_ahead_behind(&unrelated_local, &unrelated_upstream)?,
+            (1, 2),
+            "ancestors of the merge base that were reached from one side first due to clock skew aren't counted"
+        );
+        assert_eq!(graph.count_reachable(Some(unrelated_local), &[unrelated_upstream])?, 1);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn ancestors_parallel() -> crate::Result {
+    use gix_revwalk::graph::{ancestors::Sorting, Commit};
+    let store = odb_of("make_repo_with_many_branches.sh");
+    let tips = std::fs::read_dir(fixture_path("make_repo_with_many_branches.sh").join(".git/refs/heads"))?
+        .map(|entry| -> crate::Result<_> { Ok(hex_to_id(std::fs::read_to_string(entry?.path())?.trim())) })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(tips.len(), 101, "100 branches and main");
+
+    for has_cache in [false, true] {
+        let cache = || has_cache.then(|| commit_graph_of("make_repo_with_many_branches.sh"));
+        let mut graph = gix_revwalk::Graph::<Commit<()>>::new(&store, cache());
+        let mut expected = graph
+            .ancestors(tips.iter().copied(), Sorting::ByCommitTime, None)?
+            .collect::<Result<Vec<_>, _>>()?;
+        expected.sort();
+        assert_eq!(expected.len(), 201);
+        let expected_commits = graph.detach();
+
+        let mut first_order = None;
+        for thread_limit in [1, 2, 4] {
+            let mut graph = gix_revwalk::Graph::<Commit<()>>::new(&store, cache());
+            let ids = graph.ancestors_parallel(
+                tips.iter().copied().chain(Some(gix_hash::Kind::Sha1.null())),
+                store.clone(),
+                Some(thread_limit),
+            )?;
+            let first_order = first_order.get_or_insert_with(|| ids.clone());
+            assert_eq!(
+                &ids, first_order,
+                "the order is the same no matter how many threads are used"
+            );
+            assert_eq!(
+                ids[..tips.len()],
+                tips[..],
+                "tips come first, and missing ones are skipped"
+            );
+
+            let mut sorted = ids.clone();
+            sorted.sort();
+            assert_eq!(sorted, expected, "the same commits are visited as in a sequential walk");
+            let commits = graph.detach();
+            assert_eq!(commits.len(), expected_commits.len());
+            for (id, expected) in &expected_commits {
+                let actual = &commits[id];
+                assert_eq!(
+                    (&actual.parents, actual.commit_time, actual.generation),
+                    (&expected.parents, expected.commit_time, expected.generation),
+                    "the commits in the graph are the same as well"
+                );
+            }
+        }
+    }
+    Ok(())
+}
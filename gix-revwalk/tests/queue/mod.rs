@@ -0,0 +1,147 @@
+use gix_revwalk::{queue::PeekMut, PriorityQueue};
+
+#[test]
+fn peek_mut() {
+    let mut queue: PriorityQueue<u32, &str> = [(1, "a"), (3, "c"), (2, "b")].into_iter().collect();
+    assert_eq!(queue.peek(), Some((&3, &"c")));
+
+    {
+        let mut top = queue.peek_mut().expect("not empty");
+        assert_eq!((top.key(), top.value()), (&3, &"c"));
+        *top.value_mut() = "c2";
+    }
+    assert_eq!(queue.peek(), Some((&3, &"c2")), "values can be changed in place");
+
+    *queue.peek_mut().expect("not empty").key_mut() = 0;
+    assert_eq!(
+        queue.peek(),
+        Some((&2, &"b")),
+        "changing the key re-sorts the item once the guard is dropped"
+    );
+
+    let top = queue.peek_mut().expect("not empty");
+    assert_eq!(PeekMut::pop(top), (2, "b"));
+    assert_eq!(queue.pop(), Some((1, "a")));
+    assert_eq!(queue.pop(), Some((0, "c2")));
+    assert!(queue.peek_mut().is_none(), "nothing to peek at in an empty queue");
+}
+
+#[test]
+fn iter_and_into_vec() {
+    let queue: PriorityQueue<u32, &str> = [(1, "a"), (3, "c"), (2, "b")].into_iter().collect();
+    let mut items: Vec<_> = queue.iter().map(|(key, value)| (*key, *value)).collect();
+    items.sort();
+    assert_eq!(
+        items,
+        [(1, "a"), (2, "b"), (3, "c")],
+        "all items are returned, in arbitrary order"
+    );
+    assert_eq!(queue.len(), 3, "iterating doesn't change the queue");
+
+    let mut items = queue.into_vec();
+    items.sort();
+    assert_eq!(items, [(1, "a"), (2, "b"), (3, "c")]);
+    assert!(PriorityQueue::<u32, ()>::new().into_vec().is_empty());
+}
+
+#[test]
+fn into_iter_sorted() {
+    let queue: PriorityQueue<u32, &str> = [(1, "a"), (3, "c"), (2, "b")].into_iter().collect();
+    let mut iter = queue.clone().into_iter_sorted();
+    assert_eq!(iter.len(), 3, "the amount of items is known upfront");
+    assert_eq!(iter.next(), Some((3, "c")));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.collect::<Vec<_>>(),
+        [(2, "b"), (1, "a")],
+        "items are returned in the same order as when popping them"
+    );
+
+    let mut queue = queue;
+    let popped: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+    assert_eq!(popped, [(3, "c"), (2, "b"), (1, "a")]);
+    assert_eq!(PriorityQueue::<u32, ()>::new().into_iter_sorted().next(), None);
+}
+
+#[test]
+fn drain_equal_to_top() {
+    let mut queue: PriorityQueue<u32, &str> = [(1, "a"), (3, "c1"), (2, "b"), (3, "c2"), (1, "a2")]
+        .into_iter()
+        .collect();
+    let sorted = |mut items: Vec<(u32, &'static str)>| {
+        items.sort();
+        items
+    };
+    assert_eq!(
+        sorted(queue.drain_equal_to_top()),
+        [(3, "c1"), (3, "c2")],
+        "all items with the highest key are returned together"
+    );
+    assert_eq!(
+        queue.drain_equal_to_top(),
+        [(2, "b")],
+        "a single item is returned alone"
+    );
+    assert_eq!(sorted(queue.drain_equal_to_top()), [(1, "a"), (1, "a2")]);
+    assert!(queue.is_empty());
+    assert!(
+        queue.drain_equal_to_top().is_empty(),
+        "nothing is returned if the queue is empty"
+    );
+}
+
+#[test]
+fn capacity_and_bulk_insertion() {
+    let mut queue = PriorityQueue::<u32, u32>::with_capacity(10);
+    assert!(queue.capacity() >= 10);
+    queue.reserve(100);
+    assert!(queue.capacity() >= 100);
+
+    let items: Vec<_> = (0..1000u32).map(|n| ((n * 7919) % 101, n)).collect();
+    let mut incremental = PriorityQueue::new();
+    for (key, value) in items.iter().copied() {
+        incremental.insert(key, value);
+    }
+    let keys = |queue: PriorityQueue<u32, u32>| queue.into_iter_sorted().map(|(key, _)| key).collect::<Vec<_>>();
+    let expected = keys(incremental);
+
+    assert_eq!(
+        keys(items.iter().copied().collect()),
+        expected,
+        "building the queue at once yields the same order as inserting one by one"
+    );
+
+    let (first, second) = items.split_at(10);
+    let mut extended: PriorityQueue<_, _> = first.iter().copied().collect();
+    extended.extend(second.iter().copied());
+    assert_eq!(keys(extended), expected, "the same is true when extending the queue");
+}
+
+#[test]
+fn len_is_empty_and_clear() {
+    let mut queue: PriorityQueue<u32, ()> = (0..100).map(|key| (key, ())).collect();
+    assert_eq!(queue.len(), 100);
+    assert!(!queue.is_empty());
+
+    let capacity = queue.capacity();
+    queue.clear();
+    assert_eq!(queue.len(), 0);
+    assert!(queue.is_empty());
+    assert_eq!(
+        queue.capacity(),
+        capacity,
+        "the queue can be reused without reallocating"
+    );
+}
+
+#[test]
+fn append() {
+    let mut queue: PriorityQueue<u32, &str> = [(1, "a"), (4, "d")].into_iter().collect();
+    let mut other: PriorityQueue<u32, &str> = [(3, "c"), (2, "b"), (5, "e")].into_iter().collect();
+    queue.append(&mut other);
+    assert!(other.is_empty(), "all items were moved");
+    assert_eq!(
+        queue.into_iter_sorted().collect::<Vec<_>>(),
+        [(5, "e"), (4, "d"), (3, "c"), (2, "b"), (1, "a")]
+    );
+}
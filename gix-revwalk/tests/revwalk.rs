@@ -1,12 +1,8 @@
-mod graph {
-    mod commit {
-        #[test]
-        fn size_of_commit() {
-            assert_eq!(
-                std::mem::size_of::<gix_revwalk::graph::Commit<()>>(),
-                48,
-                "We might see quite a lot of these, so they shouldn't grow unexpectedly"
-            )
-        }
-    }
+mod graph;
+mod queue;
+
+pub type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + 'static>>;
+
+fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
+    gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
 }
@@ -31,7 +31,11 @@ pub mod describe {
     use gix_hash::ObjectId;
     use gix_hashtable::HashMap;
 
-    use crate::{bstr::BStr, ext::ObjectIdExt, Repository};
+    use crate::{
+        bstr::{BStr, ByteSlice},
+        ext::ObjectIdExt,
+        Repository,
+    };
 
     /// The result of [`try_resolve()`][Platform::try_resolve()].
     pub struct Resolution<'repo> {
@@ -96,6 +100,9 @@ pub mod describe {
         /// Use all tags for names, annotated or plain reference.
         AllTags,
         /// Use all references, including local branch names.
+        ///
+        /// Like `git describe --all`, names are prefixed with their category, like `heads/main`, `tags/v1.0` or `remotes/origin/main`,
+        /// to make clear which kind of reference they refer to.
         AllRefs,
     }
 
@@ -125,7 +132,13 @@ pub mod describe {
                             peeled_id.inner,
                             prio,
                             tag_time,
-                            Cow::from(r.inner.name.shorten().to_owned()),
+                            Cow::from(match self {
+                                SelectRef::AllRefs => {
+                                    let name = r.inner.name.as_bstr();
+                                    name.strip_prefix(b"refs/").unwrap_or(name).as_bstr().to_owned()
+                                }
+                                _ => r.inner.name.shorten().to_owned(),
+                            }),
                         )
                             .into()
                     })
@@ -232,6 +245,7 @@ pub mod describe {
                     fallback_to_oid: self.id_as_fallback,
                     first_parent: self.first_parent,
                     max_candidates: self.max_candidates,
+                    ..Default::default()
                 },
             )?;
 
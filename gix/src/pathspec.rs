@@ -26,6 +26,66 @@ pub mod init {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod from_file {
+    /// The error returned by [`Pathspec::from_file()`](super::Pathspec::from_file()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read pathspecs from '{}'", path.display())]
+        Io {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+        #[error("Could not split the pathspecs in '{}'", path.display())]
+        Split {
+            source: gix_pathspec::file::split::Error,
+            path: std::path::PathBuf,
+        },
+        #[error(transparent)]
+        Init(#[from] super::init::Error),
+    }
+}
+
+/// Describe how a path relates to a pathspec, as returned by [`Pathspec::match_kind()`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Ord, PartialOrd)]
+pub enum MatchKind {
+    /// The path is matched by the pathspec itself, for instance `dir/file` matched by `dir/`, `dir/file` or `dir/*`.
+    Full,
+    /// The path isn't matched, but it is a directory leading up to what could be matched,
+    /// like `dir` for the pathspec `dir/sub/file`. Hence, it's worth recursing into it.
+    Prefix,
+    /// The path was matched by an exclusion pathspec, like `:!dir/file`.
+    Excluded,
+}
+
+/// Various aggregate numbers collected while matching paths with a [`Pathspec`], once [enabled](Pathspec::enable_statistics()).
+///
+/// They are useful to learn why a pathspec didn't match, for instance to tell users which of their patterns didn't match any path.
+///
+/// Only paths that are matched to learn if they are included are counted, that is paths passed to
+/// [`pattern_matching_relative_path()`](Pathspec::pattern_matching_relative_path()) and [`is_included()`](Pathspec::is_included()),
+/// as well as the entries matched by [`index_entries_with_paths()`](Pathspec::index_entries_with_paths()) and
+/// [`tree_entries_with_paths()`](Pathspec::tree_entries_with_paths()).
+/// Directories that are only matched to decide whether to enter them, like by [`match_kind()`](Pathspec::match_kind())
+/// or while traversing trees, aren't counted, and neither are paths passed to [`all_pattern_matches()`](Pathspec::all_pattern_matches())
+/// or to a [`PathspecDetached`], which doesn't collect statistics.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct Statistics {
+    /// The amount of paths that were matched against the pathspec.
+    pub paths: usize,
+    /// The amount of paths that were matched by each pattern, indexed by the pattern's position in the list of patterns
+    /// the pathspec was created with, which is its [sequence number](search::Match::sequence_number).
+    ///
+    /// Only the pattern that decides if a path is included or excluded is counted.
+    pub matches_by_pattern: Vec<usize>,
+    /// The amount of paths that were excluded by an exclusion pattern, like `:!dir/file`.
+    pub excluded: usize,
+    /// The amount of times attributes were looked up to match patterns that specify attributes, like `:(attr:text)`.
+    pub attribute_lookups: usize,
+}
+
 /// Lifecycle
 impl<'repo> Pathspec<'repo> {
     /// Create a new instance by parsing `patterns` into [`Pathspecs`](Pattern) to make them usable for searches.
@@ -79,8 +139,42 @@ impl<'repo> Pathspec<'repo> {
             repo,
             search,
             stack: cache,
+            statistics: None,
         })
     }
+    /// Create a new instance from the pathspecs in the file at `path`, just like `git <cmd> --pathspec-from-file=<path>` would,
+    /// with one pathspec per line, or separated by NUL bytes if `nul_separated` is `true` like with `--pathspec-file-nul`.
+    ///
+    /// See [`gix_pathspec::file::split()`] for details on how the file is read, and note that `#` doesn't start a comment.
+    /// For all other parameters, see [`new()`](Self::new()).
+    pub fn from_file(
+        repo: &'repo Repository,
+        empty_patterns_match_prefix: bool,
+        path: impl Into<std::path::PathBuf>,
+        nul_separated: bool,
+        inherit_ignore_case: bool,
+        make_attributes: impl FnOnce() -> Result<gix_worktree::Stack, Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Result<Self, from_file::Error> {
+        let path = path.into();
+        let input = match std::fs::read(&path) {
+            Ok(input) => input,
+            Err(source) => return Err(from_file::Error::Io { source, path }),
+        };
+        let patterns = match gix_pathspec::file::split(&input, nul_separated).collect::<Result<Vec<_>, _>>() {
+            Ok(patterns) => patterns,
+            Err(source) => return Err(from_file::Error::Split { source, path }),
+        };
+        let mut pathspec = Self::new(
+            repo,
+            empty_patterns_match_prefix,
+            patterns,
+            inherit_ignore_case,
+            make_attributes,
+        )?;
+        pathspec.search.source = Some(path);
+        Ok(pathspec)
+    }
+
     /// Turn ourselves into the functional parts for direct usage.
     /// Note that the [`cache`](AttributeStack) is only set if one of the [`search` patterns](Search)
     /// is specifying attributes to match for.
@@ -99,6 +193,20 @@ impl<'repo> Pathspec<'repo> {
             odb: self.repo.objects.clone().into_arc()?,
         })
     }
+
+    /// Return a new instance that matches only the paths that are matched by both `self` and `other`, or `None` if there is no
+    /// path that both could match, for instance to restrict user-provided pathspecs to a scope imposed by the application.
+    ///
+    /// Note that this is [conservative](Search::intersect()) as patterns with wildcards can't generally be intersected,
+    /// so the result may match fewer paths than a precise intersection would, but never more.
+    pub fn intersect(&self, other: &Pathspec<'_>) -> Option<Self> {
+        Some(Self {
+            repo: self.repo,
+            search: self.search.intersect(&other.search)?,
+            stack: self.stack.clone().or_else(|| other.stack.clone()),
+            statistics: None,
+        })
+    }
 }
 
 /// Access
@@ -113,6 +221,22 @@ impl<'repo> Pathspec<'repo> {
         &self.search
     }
 
+    /// Start collecting [statistics](Statistics) about all subsequent matches, or reset them if they were enabled already.
+    ///
+    /// See [`Statistics`] for which matches are counted.
+    pub fn enable_statistics(&mut self) -> &mut Self {
+        self.statistics = Some(Statistics {
+            matches_by_pattern: vec![0; self.search.patterns().len()],
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Return the statistics collected since they were [enabled](Self::enable_statistics()), or `None` if they are disabled.
+    pub fn statistics(&self) -> Option<&Statistics> {
+        self.statistics.as_ref()
+    }
+
     /// Return the first [`Match`](search::Match) of `relative_path`, or `None`.
     /// Note that the match might [be excluded](search::Match::is_excluded()).
     /// `is_dir` is true if `relative_path` is a directory.
@@ -130,17 +254,27 @@ impl<'repo> Pathspec<'repo> {
         relative_path: impl Into<&'a BStr>,
         is_dir: Option<bool>,
     ) -> Option<gix_pathspec::search::Match<'_>> {
-        self.search.pattern_matching_relative_path(
-            relative_path.into(),
-            is_dir,
-            &mut |relative_path, case, is_dir, out| {
+        self.match_relative_path(relative_path.into(), is_dir, true)
+    }
+
+    /// Return all [matches](search::Match) of `relative_path` in order of precedence, or an empty list if there is none.
+    /// The first match, if present, is the one returned by [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path()),
+    /// which decides if `relative_path` is [excluded](search::Match::is_excluded()).
+    /// `is_dir` is true if `relative_path` is a directory.
+    #[momo]
+    pub fn all_pattern_matches<'a>(
+        &mut self,
+        relative_path: impl Into<&'a BStr>,
+        is_dir: Option<bool>,
+    ) -> Vec<gix_pathspec::search::Match<'_>> {
+        self.search
+            .all_pattern_matches(relative_path.into(), is_dir, &mut |relative_path, case, is_dir, out| {
                 let stack = self.stack.as_mut().expect("initialized in advance");
                 stack
                     .set_case(case)
                     .at_entry(relative_path, Some(is_dir_to_mode(is_dir)), &self.repo.objects)
                     .map_or(false, |platform| platform.matching_attributes(out))
-            },
-        )
+            })
     }
 
     /// The simplified version of [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path()) which returns
@@ -151,6 +285,25 @@ impl<'repo> Pathspec<'repo> {
             .map_or(false, |m| !m.is_excluded())
     }
 
+    /// Return how `relative_path` relates to this pathspec, or `None` if it can't possibly match.
+    /// `is_dir` is true if `relative_path` is a directory.
+    ///
+    /// Use this to decide whether to recurse into directories during a tree traversal, as only
+    /// [`Prefix`](MatchKind::Prefix) matches are directories that are merely leading up to a match.
+    #[momo]
+    pub fn match_kind<'a>(&mut self, relative_path: impl Into<&'a BStr>, is_dir: Option<bool>) -> Option<MatchKind> {
+        let relative_path = relative_path.into();
+        if let Some(m) = self.match_relative_path(relative_path, is_dir, false) {
+            return Some(if m.is_excluded() {
+                MatchKind::Excluded
+            } else {
+                MatchKind::Full
+            });
+        }
+        (is_dir == Some(true) && self.search.can_match_relative_path(relative_path, is_dir))
+            .then_some(MatchKind::Prefix)
+    }
+
     /// Return an iterator over all entries along with their path if the path matches the pathspec, or `None` if the pathspec is
     /// known to match no entry.
     // TODO: tests
@@ -165,6 +318,69 @@ impl<'repo> Pathspec<'repo> {
             })
         })
     }
+
+    /// Traverse `tree` recursively and return all of its non-tree entries whose path matches the pathspec, in breadth-first order.
+    ///
+    /// Subtrees are only entered if the pathspec can match paths within them, and subtrees that are excluded as a whole
+    /// are skipped as well, so that only the trees leading up to matching entries have to be read.
+    ///
+    /// Each returned [entry](gix_traverse::tree::recorder::Entry) provides its path along with its mode and object id.
+    /// They are collected instead of being returned as iterator as the traversal calls into a
+    /// [delegate](gix_traverse::tree::Visit) for each entry, and can't be suspended in between.
+    #[doc(alias = "match_tree", alias = "git2")]
+    pub fn tree_entries_with_paths(
+        &mut self,
+        tree: &crate::Tree<'_>,
+    ) -> Result<Vec<gix_traverse::tree::recorder::Entry>, gix_traverse::tree::breadthfirst::Error> {
+        let mut delegate = TreeFilter {
+            pathspec: self,
+            path: Default::default(),
+            records: Vec::new(),
+        };
+        tree.traverse().breadthfirst(&mut delegate)?;
+        Ok(delegate.records)
+    }
+
+    /// Like [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path()), but only update the statistics
+    /// if `update_statistics` is `true`, which shouldn't be the case if `relative_path` is only matched to prune directories.
+    fn match_relative_path(
+        &mut self,
+        relative_path: &BStr,
+        is_dir: Option<bool>,
+        update_statistics: bool,
+    ) -> Option<gix_pathspec::search::Match<'_>> {
+        let m = self.search.pattern_matching_relative_path(
+            relative_path,
+            is_dir,
+            &mut |relative_path, case, is_dir, out| {
+                if let Some(stats) = self.statistics.as_mut().filter(|_| update_statistics) {
+                    stats.attribute_lookups += 1;
+                }
+                let stack = self.stack.as_mut().expect("initialized in advance");
+                stack
+                    .set_case(case)
+                    .at_entry(relative_path, Some(is_dir_to_mode(is_dir)), &self.repo.objects)
+                    .map_or(false, |platform| platform.matching_attributes(out))
+            },
+        );
+        if let Some(stats) = self.statistics.as_mut().filter(|_| update_statistics) {
+            stats.paths += 1;
+            if let Some(m) = &m {
+                // Empty paths match without consulting any pattern.
+                if let Some(count) = stats
+                    .matches_by_pattern
+                    .get_mut(m.sequence_number)
+                    .filter(|_| !relative_path.is_empty())
+                {
+                    *count += 1;
+                }
+                if m.is_excluded() {
+                    stats.excluded += 1;
+                }
+            }
+        }
+        m
+    }
 }
 
 /// Access
@@ -199,6 +415,26 @@ impl PathspecDetached {
         )
     }
 
+    /// Return all [matches](search::Match) of `relative_path` in order of precedence, or an empty list if there is none.
+    /// The first match, if present, is the one returned by [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path()),
+    /// which decides if `relative_path` is [excluded](search::Match::is_excluded()).
+    /// `is_dir` is true if `relative_path` is a directory.
+    #[momo]
+    pub fn all_pattern_matches<'a>(
+        &mut self,
+        relative_path: impl Into<&'a BStr>,
+        is_dir: Option<bool>,
+    ) -> Vec<gix_pathspec::search::Match<'_>> {
+        self.search
+            .all_pattern_matches(relative_path.into(), is_dir, &mut |relative_path, case, is_dir, out| {
+                let stack = self.stack.as_mut().expect("initialized in advance");
+                stack
+                    .set_case(case)
+                    .at_entry(relative_path, Some(is_dir_to_mode(is_dir)), &self.odb)
+                    .map_or(false, |platform| platform.matching_attributes(out))
+            })
+    }
+
     /// The simplified version of [`pattern_matching_relative_path()`](Self::pattern_matching_relative_path()) which returns
     /// `true` if `relative_path` is included in the set of positive pathspecs, while not being excluded.
     #[momo]
@@ -215,3 +451,61 @@ fn is_dir_to_mode(is_dir: bool) -> gix_index::entry::Mode {
         gix_index::entry::Mode::FILE
     }
 }
+
+/// A [`Visit`](gix_traverse::tree::Visit) implementation that only records entries matching `pathspec`,
+/// and skips all trees that can't contain matching entries.
+struct TreeFilter<'a, 'repo> {
+    pathspec: &'a mut Pathspec<'repo>,
+    path: gix_traverse::tree::Recorder,
+    records: Vec<gix_traverse::tree::recorder::Entry>,
+}
+
+impl gix_traverse::tree::Visit for TreeFilter<'_, '_> {
+    fn pop_front_tracked_path_and_set_current(&mut self) {
+        self.path.pop_front_tracked_path_and_set_current();
+    }
+
+    fn push_back_tracked_path_component(&mut self, component: &BStr) {
+        self.path.push_back_tracked_path_component(component);
+    }
+
+    fn push_path_component(&mut self, component: &BStr) {
+        self.path.push_path_component(component);
+    }
+
+    fn pop_path_component(&mut self) {
+        self.path.pop_path_component();
+    }
+
+    fn visit_tree(&mut self, _entry: &gix_object::tree::EntryRef<'_>) -> gix_traverse::tree::visit::Action {
+        use gix_traverse::tree::visit::Action;
+        let path = self.path.path();
+        if let Some(m) = self.pathspec.match_relative_path(path, Some(true), false) {
+            // Wildcards and attributes that exclude a directory don't necessarily exclude everything in it.
+            let excludes_all_children = m.is_excluded()
+                && m.kind != gix_pathspec::search::MatchKind::WildcardMatch
+                && m.pattern.attributes.is_empty();
+            return if excludes_all_children {
+                Action::Skip
+            } else {
+                Action::Continue
+            };
+        }
+        if self.pathspec.search.can_match_relative_path(path, Some(true)) {
+            Action::Continue
+        } else {
+            Action::Skip
+        }
+    }
+
+    fn visit_nontree(&mut self, entry: &gix_object::tree::EntryRef<'_>) -> gix_traverse::tree::visit::Action {
+        if self.pathspec.is_included(self.path.path(), Some(false)) {
+            self.records.push(gix_traverse::tree::recorder::Entry {
+                mode: entry.mode,
+                filepath: self.path.path_clone(),
+                oid: entry.oid.to_owned(),
+            });
+        }
+        gix_traverse::tree::visit::Action::Continue
+    }
+}
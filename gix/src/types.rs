@@ -236,6 +236,8 @@ pub struct Pathspec<'repo> {
     pub(crate) stack: Option<gix_worktree::Stack>,
     /// The prepared search to use for checking matches.
     pub(crate) search: gix_pathspec::Search,
+    /// Aggregate numbers about all matches, if enabled.
+    pub(crate) statistics: Option<crate::pathspec::Statistics>,
 }
 
 /// Like [`Pathspec`], but without a Repository reference and with minimal API.
@@ -25,7 +25,10 @@ mod describe {
                 .expect("resolution")
                 .format_with_dirty_suffix("dirty".to_owned())?
                 .to_string();
-            assert_eq!(actual, "main-dirty");
+            assert_eq!(
+                actual, "heads/main-dirty",
+                "all refs are prefixed like in `git describe --all`"
+            );
             Ok(())
         }
 
@@ -41,7 +44,7 @@ mod describe {
                 .expect("resolution")
                 .format_with_dirty_suffix("dirty".to_owned())?
                 .to_string();
-            assert_eq!(actual, "main");
+            assert_eq!(actual, "heads/main");
             Ok(())
         }
     }
@@ -52,7 +55,11 @@ mod describe {
         let mut describe = repo.head_commit()?.describe();
         for filter in &[AnnotatedTags, AllTags, AllRefs] {
             describe = describe.names(*filter);
-            assert_eq!(describe.format()?.to_string(), "v4", "{filter:?}");
+            let expected = match filter {
+                AllRefs => "tags/v4",
+                _ => "v4",
+            };
+            assert_eq!(describe.format()?.to_string(), expected, "{filter:?}");
         }
         Ok(())
     }
@@ -64,7 +71,11 @@ mod describe {
         let mut describe = commit.describe();
         for filter in &[AnnotatedTags, AllTags, AllRefs] {
             describe = describe.names(*filter);
-            assert_eq!(describe.format()?.to_string(), "v1", "{filter:?}");
+            let expected = match filter {
+                AllRefs => "tags/v1",
+                _ => "v1",
+            };
+            assert_eq!(describe.format()?.to_string(), expected, "{filter:?}");
         }
         Ok(())
     }
@@ -78,7 +89,8 @@ mod describe {
             describe = describe.names(*filter);
             let expected = match filter {
                 AnnotatedTags => None,
-                _ => Some("l0"),
+                AllTags => Some("l0"),
+                AllRefs => Some("tags/l0"),
             };
             let actual = describe.try_format()?.map(|f| f.to_string());
             assert_eq!(actual.as_deref(), expected, "{filter:?}");
@@ -0,0 +1,9 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+mkdir -p dir/gen dir/sub other/deep
+touch a.rs dir/file.rs dir/readme dir/gen/x.rs dir/sub/y.rs other/deep/file.rs
+git add .
+git commit -q -m c1
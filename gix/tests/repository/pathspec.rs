@@ -1,4 +1,4 @@
-use gix::{bstr::ByteSlice, config::tree::gitoxide};
+use gix::{bstr::ByteSlice, config::tree::gitoxide, pathspec::MatchKind};
 use gix_worktree::stack::state::attributes::Source;
 
 use crate::util::named_repo;
@@ -39,3 +39,324 @@ fn defaults_are_taken_from_repo_config() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn match_kind_distinguishes_prefixes_from_full_matches() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let mut pathspec = repo.pathspec(
+        true,
+        ["dir/sub", ":!dir/sub/excluded"],
+        false,
+        &**repo.index()?,
+        Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+    )?;
+    assert_eq!(
+        pathspec.match_kind("dir", Some(true)),
+        Some(MatchKind::Prefix),
+        "directories leading up to the pattern are worth recursing into"
+    );
+    assert_eq!(
+        pathspec.match_kind("dir", Some(false)),
+        None,
+        "files can't be prefixes of anything"
+    );
+    assert_eq!(
+        pathspec.match_kind("dir/sub", Some(true)),
+        Some(MatchKind::Full),
+        "directories can be matched as well"
+    );
+    assert_eq!(pathspec.match_kind("dir/sub/file", Some(false)), Some(MatchKind::Full));
+    assert_eq!(
+        pathspec.match_kind("dir/sub/excluded", Some(false)),
+        Some(MatchKind::Excluded)
+    );
+    assert_eq!(pathspec.match_kind("other", Some(true)), None);
+    Ok(())
+}
+
+#[test]
+fn intersect() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let index = repo.index()?;
+    let pathspec = |patterns: &[&str]| {
+        repo.pathspec(
+            true,
+            patterns.iter().copied(),
+            false,
+            &index,
+            Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+        )
+    };
+    let mut intersection = pathspec(&["src/"])?
+        .intersect(&pathspec(&["src/lib.rs"])?)
+        .expect("src/lib.rs is in src/");
+    assert!(intersection.is_included("src/lib.rs", Some(false)));
+    assert!(!intersection.is_included("src/main.rs", Some(false)));
+    assert!(!intersection.is_included("lib.rs", Some(false)));
+
+    assert!(
+        pathspec(&["src/"])?.intersect(&pathspec(&["tests/"])?).is_none(),
+        "disjoint pathspecs can't match anything"
+    );
+    Ok(())
+}
+
+#[test]
+fn from_file() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let no_attributes = || unreachable!("no pathspec uses attributes");
+
+    let path = tmp.path().join("specs");
+    std::fs::write(&path, "hi\n\":!hi\\160\"\n")?;
+    let mut pathspec = gix::Pathspec::from_file(&repo, true, &path, false, false, no_attributes)?;
+    assert_eq!(
+        pathspec.search().source.as_deref(),
+        Some(path.as_path()),
+        "the source is remembered"
+    );
+    assert!(pathspec.is_included("hi", Some(false)));
+    assert!(!pathspec.is_included("ho", Some(false)));
+    assert!(
+        !pathspec.is_included("hip", Some(false)),
+        "quoted lines are unquoted and can be excludes as well"
+    );
+
+    std::fs::write(&path, "hi\0:!hip\0")?;
+    let mut pathspec = gix::Pathspec::from_file(&repo, true, &path, true, false, no_attributes)?;
+    assert!(pathspec.is_included("hi", Some(false)));
+    assert!(!pathspec.is_included("hip", Some(false)));
+
+    std::fs::write(&path, "")?;
+    let mut pathspec = gix::Pathspec::from_file(&repo, true, &path, false, false, no_attributes)?;
+    assert!(
+        pathspec.is_included("anything", Some(false)),
+        "no pathspec matches everything"
+    );
+
+    std::fs::write(&path, "hi\n\n")?;
+    assert!(
+        matches!(
+            gix::Pathspec::from_file(&repo, true, &path, false, false, no_attributes),
+            Err(gix::pathspec::from_file::Error::Init(_))
+        ),
+        "empty lines are invalid pathspecs"
+    );
+    assert!(matches!(
+        gix::Pathspec::from_file(&repo, true, tmp.path().join("missing"), false, false, no_attributes),
+        Err(gix::pathspec::from_file::Error::Io { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn all_pattern_matches() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let mut pathspec = repo.pathspec(
+        true,
+        ["dir/", "dir/*.rs", ":!dir/gen*"],
+        false,
+        &**repo.index()?,
+        Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+    )?;
+    let summarize = |matches: Vec<gix::pathspec::search::Match<'_>>| {
+        matches
+            .into_iter()
+            .map(|m| (m.sequence_number, m.is_excluded()))
+            .collect::<Vec<_>>()
+    };
+    let expected = [(2, true), (0, false), (1, false)];
+    assert_eq!(
+        summarize(pathspec.all_pattern_matches("dir/generated.rs", Some(false))),
+        expected,
+        "the winning exclude comes first"
+    );
+    assert_eq!(
+        pathspec
+            .pattern_matching_relative_path("dir/generated.rs", Some(false))
+            .map(|m| m.sequence_number),
+        Some(2)
+    );
+    assert_eq!(
+        summarize(pathspec.all_pattern_matches("dir/lib.rs", Some(false))),
+        [(0, false), (1, false)]
+    );
+    assert_eq!(summarize(pathspec.all_pattern_matches("other", Some(false))), []);
+
+    let mut pathspec = pathspec.detach()?;
+    assert_eq!(
+        summarize(pathspec.all_pattern_matches("dir/generated.rs", Some(false))),
+        expected,
+        "detached pathspecs behave the same"
+    );
+    Ok(())
+}
+
+#[test]
+fn literal_pathspecs_match_wildcards_verbatim() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let index = repo.index()?;
+    let pathspec = |pattern: &str| {
+        repo.pathspec(
+            true,
+            [pattern],
+            false,
+            &index,
+            Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+        )
+    };
+    for (path, is_dir, glob, literal) in [
+        ("dir*", true, Some(MatchKind::Prefix), Some(MatchKind::Prefix)),
+        ("dir*/a?", false, Some(MatchKind::Full), Some(MatchKind::Full)),
+        ("dir*/ab", false, Some(MatchKind::Full), None),
+        ("dirx", true, Some(MatchKind::Prefix), None),
+        ("dirx/ab", false, Some(MatchKind::Full), None),
+    ] {
+        assert_eq!(
+            pathspec("dir*/a?")?.match_kind(path, Some(is_dir)),
+            glob,
+            "glob: {path}"
+        );
+        assert_eq!(
+            pathspec(":(literal)dir*/a?")?.match_kind(path, Some(is_dir)),
+            literal,
+            "literal: {path}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn tree_entries_with_paths() -> crate::Result {
+    let repo = named_repo("make_pathspec_tree_repo.sh")?;
+    let index = repo.index()?;
+    let tree = repo.head_commit()?.tree()?;
+    let matching_paths = |patterns: &[&str]| -> crate::Result<Vec<String>> {
+        let mut pathspec = repo.pathspec(
+            true,
+            patterns.iter().copied(),
+            false,
+            &index,
+            Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+        )?;
+        let mut paths: Vec<_> = pathspec
+            .tree_entries_with_paths(&tree)?
+            .into_iter()
+            .map(|entry| {
+                assert!(!entry.mode.is_tree(), "only non-tree entries are returned");
+                entry.filepath.to_string()
+            })
+            .collect();
+        paths.sort();
+        Ok(paths)
+    };
+
+    assert_eq!(
+        matching_paths(&["dir/"])?,
+        ["dir/file.rs", "dir/gen/x.rs", "dir/readme", "dir/sub/y.rs"]
+    );
+    assert_eq!(
+        matching_paths(&["*.rs"])?,
+        [
+            "a.rs",
+            "dir/file.rs",
+            "dir/gen/x.rs",
+            "dir/sub/y.rs",
+            "other/deep/file.rs"
+        ]
+    );
+    assert_eq!(
+        matching_paths(&["dir/*.rs"])?,
+        ["dir/file.rs", "dir/gen/x.rs", "dir/sub/y.rs"]
+    );
+    assert_eq!(matching_paths(&["di*/gen"])?, Vec::<String>::new());
+    assert_eq!(
+        matching_paths(&["*.rs", ":!dir/gen", ":!other"])?,
+        ["a.rs", "dir/file.rs", "dir/sub/y.rs"],
+        "excluded directories are skipped entirely"
+    );
+    assert_eq!(matching_paths(&[":!dir"])?, ["a.rs", "other/deep/file.rs"]);
+    assert_eq!(
+        matching_paths(&[":!*/gen"])?,
+        [
+            "a.rs",
+            "dir/file.rs",
+            "dir/gen/x.rs",
+            "dir/readme",
+            "dir/sub/y.rs",
+            "other/deep/file.rs"
+        ],
+        "wildcards that exclude a directory don't exclude its content, just like in `git`"
+    );
+    Ok(())
+}
+
+#[test]
+fn statistics() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let index = repo.index()?;
+    let mut pathspec = repo.pathspec(
+        true,
+        ["hi", ":!hip", ":(attr:text)ho", "none"],
+        false,
+        &index,
+        Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+    )?;
+    assert!(pathspec.statistics().is_none(), "statistics are opt-in");
+    assert!(pathspec.is_included("hi", Some(false)));
+
+    pathspec.enable_statistics();
+    assert_eq!(
+        pathspec.statistics(),
+        Some(&gix::pathspec::Statistics {
+            matches_by_pattern: vec![0; 4],
+            ..Default::default()
+        }),
+        "previous matches aren't counted"
+    );
+    assert!(pathspec.is_included("hi", Some(false)));
+    assert!(!pathspec.is_included("hip", Some(false)));
+    assert!(
+        !pathspec.is_included("ho", Some(false)),
+        "there are no attributes in this repository"
+    );
+    assert!(!pathspec.is_included("other", Some(false)));
+    assert_eq!(
+        pathspec.statistics(),
+        Some(&gix::pathspec::Statistics {
+            paths: 4,
+            matches_by_pattern: vec![1, 1, 0, 0],
+            excluded: 1,
+            attribute_lookups: 2,
+        }),
+        "patterns are counted in the order they were given, and attributes are looked up for each path that reaches the pattern with attributes"
+    );
+
+    let before = pathspec.statistics().cloned();
+    assert_eq!(pathspec.match_kind("hi", Some(true)), Some(MatchKind::Full));
+    assert_eq!(
+        pathspec.statistics().cloned(),
+        before,
+        "matching directories to decide whether to recurse isn't counted"
+    );
+
+    let repo = named_repo("make_pathspec_tree_repo.sh")?;
+    let index = repo.index()?;
+    let mut pathspec = repo.pathspec(
+        true,
+        ["dir/", ":!dir/sub"],
+        false,
+        &index,
+        Source::WorktreeThenIdMapping.adjust_for_bare(repo.is_bare()),
+    )?;
+    pathspec.enable_statistics();
+    let entries = pathspec.tree_entries_with_paths(&repo.head_commit()?.tree()?)?;
+    let stats = pathspec.statistics().expect("enabled");
+    assert_eq!(
+        stats.matches_by_pattern,
+        [entries.len(), 0],
+        "only the matching entries are counted, not the directories that were entered or skipped"
+    );
+    assert_eq!(stats.excluded, 0, "the excluded directory isn't entered");
+    Ok(())
+}